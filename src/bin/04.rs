@@ -1,6 +1,5 @@
 #[allow(unused_imports)]
 use advent_of_code::*;
-use hashbrown::HashMap;
 
 advent_of_code::solution!(4);
 
@@ -13,67 +12,56 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 struct WordSearch {
-    map: HashMap<Pos, char>,
+    map: Grid2D<char>,
 }
 
 impl WordSearch {
     fn new(input: &str) -> Self {
-        let mut map = HashMap::new();
-        for (y, row) in input.lines().enumerate() {
-            for (x, c) in row.chars().enumerate() {
-                map.insert(pos_from(x, y), c);
-            }
-        }
+        let map = parser!(dense_grid_of("XMAS")).parse(input).unwrap();
         Self { map }
     }
 
     fn count_any_xmas(&self) -> usize {
         self.map
+            .find_all(&'X')
             .iter()
-            .filter(|(_, c)| **c == 'X')
-            .map(|(pos, _)| self.count_any_xmas_at(pos))
+            .map(|pos| self.count_any_xmas_at(pos))
             .sum()
     }
 
     fn count_any_xmas_at(&self, pos: &Pos) -> usize {
-        let mut found = 0;
-        for dy in (-1..=1) {
-            for dx in (-1..=1) {
-                if self.char(pos) == 'X'
-                    && self.char_near(pos, dx, dy) == 'M'
-                    && self.char_near(pos, dx * 2, dy * 2) == 'A'
-                    && self.char_near(pos, dx * 3, dy * 3) == 'S'
-                {
-                    found += 1;
-                }
-            }
-        }
-        found
+        DIRECTIONS8
+            .iter()
+            .filter(|dir| {
+                "XMAS"
+                    .chars()
+                    .enumerate()
+                    .all(|(n, c)| self.char(&dir.forward_n_from(pos, n as isize)) == c)
+            })
+            .count()
     }
 
     fn count_diagonal_mas(&self) -> usize {
         self.map
-            .iter()
+            .cells()
             .filter(|(pos, c)| **c == 'A' && self.is_diagonal_mas_at(pos))
             .count()
     }
 
     fn is_diagonal_mas_at(&self, pos: &Pos) -> bool {
-        let pair1 = (self.char_near(pos, -1, -1), self.char_near(pos, 1, 1));
-        let pair2 = (self.char_near(pos, -1, 1), self.char_near(pos, 1, -1));
+        let is_mas = |dir: Direction8| {
+            let pair = (
+                self.char(&dir.forward_from(pos)),
+                self.char(&dir.backward_from(pos)),
+            );
+            pair == ('M', 'S') || pair == ('S', 'M')
+        };
 
-        (pair1 == ('M', 'S') || pair1 == ('S', 'M')) && (pair2 == ('M', 'S') || pair2 == ('S', 'M'))
+        is_mas(Direction8::NorthWest) && is_mas(Direction8::NorthEast)
     }
 
     fn char(&self, pos: &Pos) -> char {
-        match self.map.get(pos) {
-            Some(c) => *c,
-            None => ' ',
-        }
-    }
-
-    fn char_near(&self, pos: &Pos, dx: isize, dy: isize) -> char {
-        self.char(&(pos.0 + dx, pos.1 + dy))
+        self.map.get(pos).copied().unwrap_or(' ')
     }
 }
 
@@ -1,6 +1,7 @@
 #[allow(unused_imports)]
 use advent_of_code::*;
 use core::option::Iter;
+use itertools::Itertools;
 use std::collections::HashSet;
 use std::iter::Filter;
 
@@ -59,18 +60,30 @@ impl PrintQueue {
         true
     }
 
-    fn fix(&self, pages: &Vec<u32>) -> Option<Vec<u32>> {
-        let mut pages = pages.clone();
-        let mut fixed = false;
-        for i2 in (1..pages.len()) {
-            for i1 in (0..i2) {
-                if self.page_ordering_rules.contains(&(pages[i2], pages[i1])) {
-                    (pages[i2], pages[i1]) = (pages[i1], pages[i2]);
-                    fixed = true;
-                }
+    fn fix(&self, pages: &[u32]) -> Option<Vec<u32>> {
+        // pages without a rule between them can come out in any order, so only sort updates
+        // that actually break a rule.
+        if self.is_in_order(pages) {
+            return None;
+        }
+        let mut rules = AdjacencyGraph::directed();
+        for page in pages {
+            rules.add_node(page);
+        }
+        for (before, after) in pages.iter().tuple_combinations() {
+            if self.page_ordering_rules.contains(&(*before, *after)) {
+                rules.add_edge(before, after);
+            } else if self.page_ordering_rules.contains(&(*after, *before)) {
+                rules.add_edge(after, before);
             }
         }
-        fixed.then_some(pages)
+        let fixed = rules
+            .topological_sort()
+            .expect("Page ordering rules must not contain a cycle")
+            .into_iter()
+            .map(|id| *rules.key(id))
+            .collect();
+        Some(fixed)
     }
 }
 
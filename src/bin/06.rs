@@ -48,29 +48,56 @@ impl Map {
 
     fn first_visits(&self) -> HashMap<Pos, Direction> {
         let mut visits = HashMap::new();
-        self.clone()._traverse(&mut visits);
+        let (mut facing, mut turns) = (self.dir, 0);
+        let walk = walk((self.pos, self.dir), |guard| self.step(guard));
+        for (pos, dir) in walk.path() {
+            if *dir != facing {
+                (facing, turns) = (*dir, turns + 1);
+                visualize(|| self.frame(&visits, pos, format!("Turn {turns}: {dir:?}")));
+            }
+            visits.try_insert(*pos, *dir);
+        }
+        if let Some((pos, _)) = walk.path().last() {
+            visualize(|| self.frame(&visits, pos, "Left the map".to_owned()));
+        }
         visits
     }
 
-    fn traverse(&mut self) -> bool {
-        self._traverse(&mut HashMap::new())
+    fn frame(&self, visits: &HashMap<Pos, Direction>, guard: &Pos, label: String) -> Frame {
+        let mut frame = Frame::new(self.bounds).label(label);
+        frame.set_all(&self.obstacles, '#');
+        for pos in visits.keys() {
+            frame.set_colored(pos, 'X', Color::YELLOW);
+        }
+        frame.set_colored(guard, '@', Color::RED);
+        frame
     }
 
-    fn _traverse(&mut self, first_visits: &mut HashMap<Pos, Direction>) -> bool {
-        let mut north_turns = HashSet::new();
+    fn traverse(&self) -> bool {
+        // only the turns need remembering, a loop repeats them as well.
+        loops((self.pos, self.dir), |guard| self.next_turn(guard))
+    }
+
+    /// Walks straight to the next obstacle and turns, or None if the guard leaves the map.
+    fn next_turn(&self, guard: &Facing) -> Option<Facing> {
+        let mut guard = *guard;
         loop {
-            first_visits.try_insert(self.pos, self.dir);
-            let in_front = self.dir.forward_from(&self.pos);
-            if self.obstacles.contains(&in_front) {
-                self.dir = self.dir.turn_right();
-                if self.dir == Direction::North && !north_turns.insert(self.pos) {
-                    return true;
-                }
-            } else if self.is_on_map(&in_front) {
-                self.pos = in_front;
-            } else {
-                return false;
+            let next = self.step(&guard)?;
+            if next.1 != guard.1 {
+                return Some(next);
             }
+            guard = next;
+        }
+    }
+
+    fn step(&self, (pos, dir): &Facing) -> Option<Facing> {
+        let in_front = dir.forward_from(pos);
+        if self.obstacles.contains(&in_front) {
+            Some((*pos, dir.turn_right()))
+        } else if self.is_on_map(&in_front) {
+            Some((in_front, *dir))
+        } else {
+            None
         }
     }
 
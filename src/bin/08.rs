@@ -28,35 +28,24 @@ impl Antenna {
         let mut set = HashSet::new();
         for (c, positions) in &self.locations.map {
             for combo in positions.iter().combinations(2) {
-                let dx = combo[0].0 - combo[1].0;
-                let dy = combo[0].1 - combo[1].1;
+                let (a, b) = (Point::from(*combo[0]), Point::from(*combo[1]));
 
                 if use_resonant_harmonics {
-                    set.insert(*combo[0]);
-                    set.insert(*combo[1]);
+                    set.insert(a.into());
+                    set.insert(b.into());
                 }
 
-                for i in (1..) {
-                    let a = (combo[1].0 - dx * i, combo[1].1 - dy * i);
-                    if self.is_on_map(&a) {
-                        set.insert(a);
-                    } else {
-                        break;
-                    }
-                    if !use_resonant_harmonics {
-                        break;
-                    }
-                }
-
-                for i in (1..) {
-                    let a = (combo[0].0 + dx * i, combo[0].1 + dy * i);
-                    if self.is_on_map(&a) {
-                        set.insert(a);
-                    } else {
-                        break;
-                    }
-                    if !use_resonant_harmonics {
-                        break;
+                for (start, delta) in [(b, b - a), (a, a - b)] {
+                    for i in (1..) {
+                        let antinode = start + delta * i;
+                        if self.is_on_map(&antinode) {
+                            set.insert(antinode.into());
+                        } else {
+                            break;
+                        }
+                        if !use_resonant_harmonics {
+                            break;
+                        }
                     }
                 }
             }
@@ -65,11 +54,9 @@ impl Antenna {
         set
     }
 
-    fn is_on_map(&self, pos: &Pos) -> bool {
-        pos.0 >= 0
-            && pos.1 >= 0
-            && pos.0 <= self.locations.bounds.0
-            && pos.1 <= self.locations.bounds.1
+    fn is_on_map(&self, point: &Point) -> bool {
+        (0..=self.locations.bounds.0).contains(&point.x)
+            && (0..=self.locations.bounds.1).contains(&point.y)
     }
 }
 
@@ -15,26 +15,14 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 struct TopoMap {
-    map: Vec<Vec<u8>>,
+    map: Grid2D<usize>,
     starts: Vec<Pos>,
 }
 
 impl TopoMap {
     fn new(input: &str) -> Self {
-        let map = input
-            .lines()
-            .map(|row| row.as_bytes().iter().map(|b| *b - b'0').collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        let mut starts = vec![];
-
-        for (y, row) in map.iter().enumerate() {
-            for (x, d) in row.iter().enumerate() {
-                if *d == 0 {
-                    starts.push(pos_from(x, y));
-                }
-            }
-        }
-
+        let map = parser!(dense_grid(digit)).parse(input).unwrap();
+        let starts = map.find_all(&0).into_iter().collect();
         Self { map, starts }
     }
 
@@ -62,39 +50,20 @@ impl TopoMap {
         let mut queue = VecDeque::from([*start]);
 
         while let Some(pos) = queue.pop_front() {
-            if let Some(h) = self.at(&pos) {
-                if h == 9 {
-                    seen.push(pos);
-                    continue;
-                }
-                for dir in DIRECTIONS {
-                    let pos2 = dir.forward_from(&pos);
-                    if let Some(h2) = self.at(&pos2) {
-                        if h2 == h + 1 {
-                            queue.push_back(pos2);
-                        }
-                    }
+            let h = self.map[pos];
+            if h == 9 {
+                seen.push(pos);
+                continue;
+            }
+            for pos2 in self.map.neighbors4(&pos) {
+                if self.map[pos2] == h + 1 {
+                    queue.push_back(pos2);
                 }
             }
         }
 
         seen
     }
-
-    #[inline]
-    fn at(&self, pos: &Pos) -> Option<u8> {
-        if pos.0 < 0 || pos.1 < 0 {
-            return None;
-        }
-
-        let x: usize = pos.0 as usize;
-        let y: usize = pos.1 as usize;
-        if y >= self.map.len() || x >= self.map[y].len() {
-            None
-        } else {
-            Some(self.map[y][x])
-        }
-    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,5 @@
 #[allow(unused_imports)]
 use advent_of_code::*;
-use memoize::memoize;
 
 advent_of_code::solution!(11);
 
@@ -21,19 +20,22 @@ fn blink_all(input: &str, times: usize) -> usize {
         .sum()
 }
 
-#[memoize]
+static BLINKS: Memo<(usize, usize), usize> = Memo::new();
+
 fn blink_single(n: usize, times: usize) -> usize {
     if times == 0 {
         return 1;
     }
 
-    if n == 0 {
-        blink_single(1, times - 1)
-    } else if let Some((left, right)) = split_even(n) {
-        blink_single(left, times - 1) + blink_single(right, times - 1)
-    } else {
-        blink_single(n * 2024, times - 1)
-    }
+    BLINKS.get_or_insert_with(&(n, times), || {
+        if n == 0 {
+            blink_single(1, times - 1)
+        } else if let Some((left, right)) = split_even(n) {
+            blink_single(left, times - 1) + blink_single(right, times - 1)
+        } else {
+            blink_single(n * 2024, times - 1)
+        }
+    })
 }
 
 fn split_even(n: usize) -> Option<(usize, usize)> {
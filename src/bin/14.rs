@@ -21,22 +21,26 @@ pub fn part_two(input: &str) -> Option<isize> {
     let mut min_s = usize::MAX;
     let mut min_n = None;
     for n in (0..(dims.0 * dims.1)) {
+        progress(n as usize, (dims.0 * dims.1) as usize);
         let s = robots.safety_score(n);
         if s < min_s {
             min_s = s;
             min_n = Some(n);
         }
     }
+    if let Some(n) = min_n {
+        visualize(|| robots.frame(n));
+    }
     min_n
 }
 
-type Robot = (Pos, Pos);
+type Robot = (Point, Point);
 
 #[derive(Debug)]
 struct Robots {
     robots: Vec<Robot>,
-    dims: Pos,
-    mid: Pos,
+    dims: Point,
+    mid: Point,
 }
 
 impl Robots {
@@ -44,43 +48,48 @@ impl Robots {
         let robots = parser!(lines(
             "p=" px:isize "," py:isize
             " v=" vx:isize "," vy:isize
-            => ((px, py), (vx, vy)),
+            => (Point::new(px, py), Point::new(vx, vy)),
         ))
         .parse(input)
         .unwrap();
-        let mid = (dims.0 / 2, dims.1 / 2);
+        let dims = Point::from(dims);
+        let mid = Point::new(dims.x / 2, dims.y / 2);
         Self { robots, dims, mid }
     }
 
     fn safety_score(&self, n: isize) -> usize {
         let mut quads = [0; 4];
-        for (p, v) in &self.robots {
-            let mut x = (p.0 + v.0 * n) % self.dims.0;
-            while x < 0 {
-                x += self.dims.0;
-            }
-            let mut y = (p.1 + v.1 * n) % self.dims.1;
-            while y < 0 {
-                y += self.dims.1;
-            }
-            if let Some(q) = self.quadrant((x, y)) {
+        for robot in &self.robots {
+            if let Some(q) = self.quadrant(self.position(robot, n)) {
                 quads[q] += 1;
             }
         }
         quads[0] * quads[1] * quads[2] * quads[3]
     }
 
-    fn quadrant(&self, pos: Pos) -> Option<usize> {
-        if pos.0 == self.mid.0 || pos.1 == self.mid.1 {
+    fn frame(&self, n: isize) -> Frame {
+        let mut frame = Frame::new((self.dims - (1, 1)).into()).label(format!("After {n}s"));
+        for robot in &self.robots {
+            frame.set_colored(&self.position(robot, n).into(), '#', Color::GREEN);
+        }
+        frame
+    }
+
+    fn position(&self, (p, v): &Robot, n: isize) -> Point {
+        (*p + *v * n).rem_euclid(&self.dims)
+    }
+
+    fn quadrant(&self, pos: Point) -> Option<usize> {
+        if pos.x == self.mid.x || pos.y == self.mid.y {
             None
-        } else if pos.0 < self.mid.0 {
-            if pos.1 < self.mid.1 {
+        } else if pos.x < self.mid.x {
+            if pos.y < self.mid.y {
                 Some(0)
             } else {
                 Some(1)
             }
         } else {
-            if pos.1 < self.mid.1 {
+            if pos.y < self.mid.y {
                 Some(2)
             } else {
                 Some(3)
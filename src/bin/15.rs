@@ -58,7 +58,9 @@ impl Warehouse {
     }
 
     fn run(mut self) -> Self {
-        for dir in self.movements.clone() {
+        for (i, dir) in self.movements.clone().into_iter().enumerate() {
+            visualize(|| self.frame(format!("Move {i}: {dir:?}")));
+
             let pos2 = dir.forward_from(&self.robot);
             let pos2w = west(&pos2);
 
@@ -82,9 +84,29 @@ impl Warehouse {
 
             self.robot = pos2;
         }
+        visualize(|| self.frame("Done".to_owned()));
         self
     }
 
+    fn frame(&self, label: String) -> Frame {
+        let bounds = self
+            .walls
+            .iter()
+            .fold((0, 0), |b, w| (b.0.max(w.0), b.1.max(w.1)));
+        let mut frame = Frame::new(bounds).label(label);
+        frame.set_all(&self.walls, '#');
+        for pos in &self.boxes {
+            if self.wide {
+                frame.set_colored(pos, '[', Color::ORANGE);
+                frame.set_colored(&east(pos), ']', Color::ORANGE);
+            } else {
+                frame.set_colored(pos, 'O', Color::ORANGE);
+            }
+        }
+        frame.set_colored(&self.robot, '@', Color::RED);
+        frame
+    }
+
     fn attempt_push(&mut self, pos: &Pos, dir: &Direction) -> bool {
         let pos2 = dir.forward_from(&pos);
         let pos2e = east(&pos2);
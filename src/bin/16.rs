@@ -4,24 +4,24 @@ use hashbrown::HashSet;
 use itertools::Itertools;
 use pathfinding::directed::dijkstra::dijkstra;
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, parse = Maze::new);
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(Maze::new(input).min_score())
+pub fn part_one(maze: &Maze) -> Option<usize> {
+    Some(maze.min_score())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Some(Maze::new(input).best_seats())
+pub fn part_two(maze: &Maze) -> Option<usize> {
+    Some(maze.best_seats())
 }
 
-struct Maze {
+pub struct Maze {
     walls: HashSet<Pos>,
     start: Pos,
     end: Pos,
 }
 
 impl Maze {
-    fn new(input: &str) -> Self {
+    pub fn new(input: &str) -> Self {
         let mut grid = parser!(grid_of(".#SE")).parse(input).unwrap();
 
         Self {
@@ -43,31 +43,21 @@ impl Maze {
         let mut successors = |&n: &Node| self.successor(n);
         let mut success = |&n: &Node| n.0 == self.end;
 
-        let (visited, _) = dijkstra_multi(&self.start(), successors, success).unwrap();
-        visited.into_iter().unique_by(|n| n.0).count()
+        // the end can be reached facing different directions at the same score.
+        let paths = dijkstra_multi_all(&self.start(), successors, success).unwrap();
+        paths.nodes().into_iter().unique_by(|n| n.0).count()
     }
 
     fn start(&self) -> Node {
         (self.start.clone(), Direction::East)
     }
 
-    fn successor<'a>(&'a self, node: Node) -> impl Iterator<Item = (Node, usize)> + 'a {
-        let (pos, dir) = node;
-        [dir, dir.turn_left(), dir.turn_right()]
-            .into_iter()
-            .filter_map(move |dir2| {
-                let cost = if dir == dir2 { 1 } else { 1001 };
-                let pos2 = dir2.forward_from(&pos);
-                if !self.walls.contains(&pos) {
-                    Some(((pos2, dir2), cost))
-                } else {
-                    None
-                }
-            })
+    fn successor(&self, node: Node) -> impl Iterator<Item = (Node, usize)> + '_ {
+        GridMoves::new(&self.walls).costs(1, 1000).turns(&node)
     }
 }
 
-type Node = (Pos, Direction);
+type Node = Facing;
 
 #[cfg(test)]
 mod tests {
@@ -75,33 +65,33 @@ mod tests {
 
     #[test]
     fn test_part_one_a() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&Maze::new(&advent_of_code::template::read_file_part(
             "examples", DAY, 1,
-        ));
+        )));
         assert_eq!(result, Some(7036));
     }
 
     #[test]
     fn test_part_one_b() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&Maze::new(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
-        ));
+        )));
         assert_eq!(result, Some(11048));
     }
 
     #[test]
     fn test_part_two_a() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&Maze::new(&advent_of_code::template::read_file_part(
             "examples", DAY, 1,
-        ));
+        )));
         assert_eq!(result, Some(45));
     }
 
     #[test]
     fn test_part_two_b() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&Maze::new(&advent_of_code::template::read_file_part(
             "examples", DAY, 2,
-        ));
+        )));
         assert_eq!(result, Some(64));
     }
 }
@@ -18,6 +18,7 @@ pub fn part_two(input: &str) -> Option<usize> {
             .map(|a| (a << partial_match.1) | partial_match.0)
             .filter(|a| *a > 0b111) // If we find a partial match that's too small it fails
             .find_map(|a| {
+                progress_message(|| format!("a = {a}, {} bits matched", partial_match.1));
                 computer
                     .with_register_a(a)
                     .run_and_match_output(&computer.code)
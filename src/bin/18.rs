@@ -2,7 +2,6 @@
 use advent_of_code::*;
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
-use pathfinding::directed::bfs::bfs;
 use rayon::prelude::*;
 
 advent_of_code::solution!(18);
@@ -18,16 +17,31 @@ const START_TIME: usize = 12;
 const START_TIME: usize = 1024;
 
 pub fn part_one(input: &str) -> Option<usize> {
-    Memory::new(input).min_path()
+    let memory = Memory::new(input);
+    let path = memory.shortest_path()?;
+    visualize(|| memory.frame(&path, format!("Time {}", memory.time)));
+    Some(path.len() - 1)
 }
 
 pub fn part_two(input: &str) -> Option<String> {
     let mut memory = Memory::new(input);
+    let counter = ProgressCounter::new(memory.time_left());
     (1..=memory.time_left())
         .into_par_iter()
-        .find_first(|t| memory.clone().advance_time(*t).min_path().is_none())
+        .find_first(|t| {
+            counter.tick();
+            memory.clone().advance_time(*t).min_path().is_none()
+        })
         .map(|time| {
             let (x, y) = memory.automaton[memory.time + time - 1];
+            visualize(|| {
+                // the last path still open, cut by the byte that falls next.
+                let before = memory.clone().advance_time(time - 1);
+                let path = before.shortest_path().unwrap_or_default();
+                let mut frame = before.frame(&path, format!("Time {}: blocked", before.time + 1));
+                frame.set_colored(&(x, y), '#', Color::RED);
+                frame
+            });
             format!("{},{}", x, y).into()
         })
 }
@@ -65,21 +79,24 @@ impl Memory {
     }
 
     fn min_path(&self) -> Option<usize> {
+        self.shortest_path().map(|path| path.len() - 1)
+    }
+
+    fn shortest_path(&self) -> Option<Vec<Pos>> {
         let start = (0, 0);
-        let mut successors = move |&pos: &Pos| {
-            DIRECTIONS.iter().filter_map(move |dir| {
-                let pos2 = dir.forward_from(&pos);
-                (is_on_map(&pos2) && !self.corrupted.contains(&pos2)).then_some(pos2)
-            })
-        };
-        let mut success = |&pos: &Pos| pos.0 == GRID_MAX && pos.1 == GRID_MAX;
-
-        bfs(&start, successors, success).map(|path| path.len() - 1)
+        let moves = GridMoves::new(&self.corrupted).bounded((GRID_MAX, GRID_MAX));
+
+        astar_pos(&start, &(GRID_MAX, GRID_MAX), |pos| moves.successors(pos)).map(|(path, _)| path)
     }
-}
 
-fn is_on_map(pos: &Pos) -> bool {
-    (0..=GRID_MAX).contains(&pos.0) && (0..=GRID_MAX).contains(&pos.1)
+    fn frame(&self, path: &[Pos], label: String) -> Frame {
+        let mut frame = Frame::new((GRID_MAX, GRID_MAX)).label(label);
+        frame.set_all(&self.corrupted, '#');
+        for pos in path {
+            frame.set_colored(pos, 'O', Color::GREEN);
+        }
+        frame
+    }
 }
 
 #[cfg(test)]
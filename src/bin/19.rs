@@ -14,6 +14,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 struct Onsen {
     towels: Vec<String>,
     arrangements: Vec<String>,
+    possible: Memo<String, usize>,
 }
 
 impl Onsen {
@@ -29,6 +30,7 @@ impl Onsen {
         Self {
             towels,
             arrangements,
+            possible: Memo::new(),
         }
     }
 
@@ -39,7 +41,7 @@ impl Onsen {
     }
 
     fn possible(&self, arrangement: &str) -> usize {
-        memoize(arrangement, || {
+        self.possible.get_or_insert_with(arrangement, || {
             self.towels
                 .iter()
                 .map(|towel| {
@@ -56,27 +58,6 @@ impl Onsen {
     }
 }
 
-// WARNING - this will bleed over between runs, so if you change
-// the input the result will be wrong.
-use hashbrown::HashMap;
-use std::sync::Mutex;
-lazy_static::lazy_static! {
-    static ref MEMO: Mutex<HashMap<String, usize>> = Mutex::new(HashMap::new());
-}
-
-fn memoize<F>(key: &str, f: F) -> usize
-where
-    F: Fn() -> usize,
-{
-    if let Some(&value) = MEMO.lock().unwrap().get(key) {
-        return value;
-    }
-
-    let value = f();
-    MEMO.lock().unwrap().insert(key.to_owned(), value);
-    value
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,25 +1,23 @@
 #[allow(unused_imports)]
 use advent_of_code::*;
-use hashbrown::{HashMap, HashSet};
-use pathfinding::directed::bfs::bfs;
-use rayon::prelude::*;
+use hashbrown::HashSet;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(20, parse = Racetrack::new);
 
 #[cfg(test)]
-const MIN_SAVE: isize = 8;
+const MIN_SAVE: usize = 8;
 #[cfg(not(test))]
-const MIN_SAVE: isize = 100;
+const MIN_SAVE: usize = 100;
 
-pub fn part_one(input: &str) -> Option<usize> {
-    Some(Racetrack::new(input).valid_cheats(2))
+pub fn part_one(track: &Racetrack) -> Option<usize> {
+    Some(track.valid_cheats(2))
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    Some(Racetrack::new(input).valid_cheats(20))
+pub fn part_two(track: &Racetrack) -> Option<usize> {
+    Some(track.valid_cheats(20))
 }
 
-struct Racetrack {
+pub struct Racetrack {
     walls: HashSet<Pos>,
     start: Pos,
     end: Pos,
@@ -27,7 +25,7 @@ struct Racetrack {
 }
 
 impl Racetrack {
-    fn new(input: &str) -> Self {
+    pub fn new(input: &str) -> Self {
         let mut grid = parser!(grid_of(".#SE")).parse(input).unwrap();
         Self {
             walls: grid.take_all('#'),
@@ -37,54 +35,12 @@ impl Racetrack {
         }
     }
 
-    fn path(&self) -> HashMap<Pos, isize> {
-        let start = self.start;
-        let mut successors = move |&pos: &Pos| {
-            DIRECTIONS.iter().filter_map(move |dir| {
-                let pos2 = dir.forward_from(&pos);
-                (!self.walls.contains(&pos2)).then_some(pos2)
-            })
-        };
-        let mut success = |&pos: &Pos| pos == self.end;
-
-        bfs(&start, successors, success)
-            .expect("No shortest path found")
-            .into_iter()
-            .enumerate()
-            .map(|(i, pos)| (pos, i as isize))
-            .collect()
-    }
-
-    fn valid_cheats(&self, max_distance: isize) -> usize {
-        let non_cheat = self.path();
-        non_cheat
-            .par_iter()
-            .map(|(pos, i)| {
-                self.valid_cheats_at(pos, max_distance, &|pos2, distance| {
-                    i + MIN_SAVE + distance <= *non_cheat.get(pos2).unwrap_or(&0)
-                })
-            })
-            .sum()
-    }
-
-    fn valid_cheats_at<F>(&self, pos: &Pos, max_distance: isize, filter: &F) -> usize
-    where
-        F: Fn(&Pos, isize) -> bool,
-    {
-        (2..=max_distance)
-            .flat_map(|distance| {
-                (-distance..distance).flat_map(move |dx| {
-                    let dy = distance - dx.abs();
-                    [(pos.0 - dx, pos.1 - dy), (pos.0 + dx, pos.1 + dy)]
-                        .into_iter()
-                        .filter(move |pos2| self.is_on_map(pos2) && filter(pos2, distance))
-                })
-            })
-            .count()
-    }
-
-    fn is_on_map(&self, pos: &Pos) -> bool {
-        pos.0 >= 0 && pos.1 >= 0 && pos.0 <= self.bounds.0 && pos.1 <= self.bounds.1
+    fn valid_cheats(&self, max_distance: usize) -> usize {
+        let moves = GridMoves::new(&self.walls).bounded(self.bounds);
+        let track = DistanceField::bfs(&self.start, self.bounds, |pos| moves.neighbors(pos));
+        let time = track.get(&self.end).expect("No shortest path found");
+        debug!("the race takes {time} picoseconds without cheating");
+        track.shortcuts(max_distance, MIN_SAVE)
     }
 }
 
@@ -94,13 +50,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&Racetrack::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&Racetrack::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(2492));
     }
 }
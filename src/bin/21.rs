@@ -2,7 +2,6 @@
 use advent_of_code::*;
 use hashbrown::HashMap;
 use itertools::Itertools;
-use memoize::memoize;
 use std::iter::{once, repeat};
 
 advent_of_code::solution!(21);
@@ -24,16 +23,19 @@ fn key_cost_checksum(codes: &str, layers: usize) -> usize {
         .lines()
         .map(|code| {
             let n = code[0..code.len() - 1].parse::<usize>().unwrap();
-            n * key_cost(code.to_owned(), layers)
+            n * key_cost(code, layers)
         })
         .sum()
 }
 
-#[memoize]
-fn key_cost(code: String, layers: usize) -> usize {
+static KEY_COSTS: Memo<(String, usize), usize> = Memo::new();
+
+fn key_cost(code: &str, layers: usize) -> usize {
     if layers == 0 {
-        code.len()
-    } else {
+        return code.len();
+    }
+
+    KEY_COSTS.get_or_insert_with(&(code.to_owned(), layers), || {
         once('A')
             .chain(code.chars())
             .tuple_windows()
@@ -42,12 +44,12 @@ fn key_cost(code: String, layers: usize) -> usize {
                     .get(&pair)
                     .unwrap()
                     .iter()
-                    .map(|subcode| key_cost(subcode.clone(), layers - 1))
+                    .map(|subcode| key_cost(subcode, layers - 1))
                     .min()
                     .unwrap()
             })
             .sum()
-    }
+    })
 }
 
 lazy_static::lazy_static! {
@@ -2,7 +2,6 @@
 use advent_of_code::*;
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
-use topological_sort::TopologicalSort;
 
 advent_of_code::solution!(24);
 
@@ -106,21 +105,23 @@ impl Wires {
 
     fn evaluate(&self) -> Vec<bool> {
         let mut wires = vec![false; self.ids.next_id()];
-        let mut ts = TopologicalSort::<usize>::new();
+        let mut circuit = AdjacencyGraph::directed();
 
         for (w3, (w1, op, w2)) in &self.rules {
+            circuit.add_node(w3);
             if !self.wires.contains_key(w1) {
-                ts.add_dependency(*w1, *w3);
+                circuit.add_edge(w1, w3);
             }
             if !self.wires.contains_key(w2) {
-                ts.add_dependency(*w2, *w3);
+                circuit.add_edge(w2, w3);
             }
         }
 
         for (w, v) in &self.wires {
             wires[*w] = *v;
         }
-        for w in ts {
+        let order = circuit.topological_sort().expect("Circuit must not loop");
+        for w in order.into_iter().map(|id| *circuit.key(id)) {
             let (w1, op, w2) = self.rules.get(&w).unwrap();
             let v1 = wires[*w1];
             let v2 = wires[*w2];
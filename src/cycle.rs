@@ -0,0 +1,250 @@
+use hashbrown::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Where the sequence `x, f(x), f(f(x)), ...` starts repeating: step `start + length` is
+/// the same state as step `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Floyd's tortoise and hare cycle detection, which only keeps two states around. The
+/// sequence must repeat eventually, otherwise this never returns.
+pub fn floyd<T, F>(initial: T, mut f: F) -> Cycle
+where
+    T: PartialEq + Clone,
+    F: FnMut(&T) -> T,
+{
+    let mut tortoise = f(&initial);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    // the hare is now a multiple of the length ahead, so both meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+    Cycle { start, length }
+}
+
+/// Brent's cycle detection, which finds the same cycle as [`floyd`] with fewer calls to `f`.
+pub fn brent<T, F>(initial: T, mut f: F) -> Cycle
+where
+    T: PartialEq + Clone,
+    F: FnMut(&T) -> T,
+{
+    // find the length by moving the tortoise to the hare at every power of two.
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = f(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+/// The state after `n` steps, simulating only until a state repeats and then jumping ahead
+/// by whole cycles:
+///
+/// ```
+/// let platform = state_at(platform, 1_000_000_000, |p| p.spin_cycle());
+/// ```
+pub fn state_at<T, F>(initial: T, n: usize, mut f: F) -> T
+where
+    T: Hash + Eq + Clone,
+    F: FnMut(&T) -> T,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    for step in 0..n {
+        if let Some(start) = seen.insert(state.clone(), step) {
+            let cycle = Cycle {
+                start,
+                length: step - start,
+            };
+            return states.swap_remove(cycle.index(n));
+        }
+        states.push(state.clone());
+        state = f(&state);
+    }
+    state
+}
+
+/// How a walk through states ended, see [`walk`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Walk<S> {
+    /// The walk stopped, e.g. by leaving the map, after visiting these states.
+    Exited(Vec<S>),
+    /// The state after the last one of `path` is `path[start]`.
+    Looped { path: Vec<S>, start: usize },
+}
+
+impl<S> Walk<S> {
+    /// The states visited, each once, in order.
+    pub fn path(&self) -> &[S] {
+        match self {
+            Walk::Exited(path) | Walk::Looped { path, .. } => path,
+        }
+    }
+
+    pub fn is_loop(&self) -> bool {
+        matches!(self, Walk::Looped { .. })
+    }
+}
+
+/// Follows `step` from `start` until it returns None or a state repeats, e.g. for a guard
+/// with a position and [`Direction`](crate::Direction) walking until it leaves the map:
+///
+/// ```
+/// let walk = walk((start, Direction::North), |guard| map.step(guard));
+/// let visited: HashSet<Pos> = walk.path().iter().map(|(pos, _)| *pos).collect();
+/// ```
+pub fn walk<S, F>(start: S, mut step: F) -> Walk<S>
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> Option<S>,
+{
+    let mut seen = HashMap::new();
+    let mut path = vec![];
+    let mut state = Some(start);
+    while let Some(current) = state {
+        if let Some(start) = seen.get(&current) {
+            return Walk::Looped {
+                start: *start,
+                path,
+            };
+        }
+        seen.insert(current.clone(), path.len());
+        state = step(&current);
+        path.push(current);
+    }
+    Walk::Exited(path)
+}
+
+/// Whether following `step` from `start` ever comes back to a state, like [`walk`] but
+/// without keeping the path.
+pub fn loops<S, F>(start: S, mut step: F) -> bool
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> Option<S>,
+{
+    let mut seen = HashSet::new();
+    let mut state = Some(start);
+    while let Some(current) = state {
+        if !seen.insert(current.clone()) {
+            return true;
+        }
+        state = step(&current);
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Direction, Heading, Pos};
+
+    // 0, 1, 2 lead into the cycle 3, 4, 5, 6, 7.
+    fn rho(x: &usize) -> usize {
+        if *x < 7 {
+            x + 1
+        } else {
+            3
+        }
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        let expected = Cycle {
+            start: 3,
+            length: 5,
+        };
+        assert_eq!(floyd(0, rho), expected);
+        assert_eq!(brent(0, rho), expected);
+        assert_eq!(
+            brent(5, rho),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+        assert_eq!(expected.index(2), 2);
+        assert_eq!(expected.index(1_000_000), 5);
+    }
+
+    #[test]
+    fn test_state_at() {
+        assert_eq!(state_at(0, 1_000_000, rho), 5);
+        assert_eq!(state_at(0, 2, rho), 2);
+        assert_eq!(state_at(0, 8, rho), 3);
+    }
+
+    #[test]
+    fn test_walk() {
+        // turning right at the edges of a 3x3 box loops, going straight leaves it.
+        let turning = |(pos, dir): &(Pos, Direction)| {
+            let ahead = dir.forward_from(pos);
+            if (0..3).contains(&ahead.0) && (0..3).contains(&ahead.1) {
+                Some((ahead, *dir))
+            } else {
+                Some((*pos, dir.turn_right()))
+            }
+        };
+        let walked = walk(((0, 0), Direction::East), turning);
+        assert!(walked.is_loop());
+        assert_eq!(walked.path().len(), 12);
+        assert!(loops(((1, 1), Direction::North), turning));
+
+        let straight = |(pos, dir): &(Pos, Direction)| {
+            let ahead = dir.forward_from(pos);
+            (ahead.0 < 3).then_some((ahead, *dir))
+        };
+        assert_eq!(walk(((0, 0), Direction::East), straight).path().len(), 3);
+        assert!(!loops(((0, 0), Direction::East), straight));
+    }
+}
@@ -35,25 +35,78 @@ impl Direction {
             _ => panic!("Invalid direction {:?}", &c),
         }
     }
+}
+
+/// Moves shared by [`Direction`] and [`Direction8`], so that helpers like [`flood_fill`]
+/// can be parameterized by connectivity:
+///
+/// ```
+/// flood_fill::<Direction8, _, _>(&start, |pos| { ... }, |pos, pos2, dir| { ... });
+/// ```
+pub trait Heading: Copy + Eq + Hash + Sized + 'static {
+    /// All directions of this kind.
+    fn all() -> &'static [Self];
+
+    /// The step taken when moving forward once.
+    fn delta(&self) -> Pos;
+
+    /// The direction of a single step, if there is one.
+    fn from_delta(delta: &Pos) -> Option<Self> {
+        Self::all()
+            .iter()
+            .find(|dir| dir.delta() == *delta)
+            .copied()
+    }
 
-    pub fn forward_from(&self, pos: &Pos) -> Pos {
+    /// An arrow pointing in this direction.
+    fn to_char(&self) -> char;
+
+    /// The next direction counterclockwise: 90° for [`Direction`], 45° for [`Direction8`].
+    fn turn_left(&self) -> Self;
+
+    /// The next direction clockwise: 90° for [`Direction`], 45° for [`Direction8`].
+    fn turn_right(&self) -> Self;
+
+    fn invert(&self) -> Self;
+
+    fn forward_from(&self, pos: &Pos) -> Pos {
         self.forward_n_from(pos, 1)
     }
 
-    pub fn backward_from(&self, pos: &Pos) -> Pos {
+    fn backward_from(&self, pos: &Pos) -> Pos {
         self.forward_n_from(pos, -1)
     }
 
-    pub fn forward_n_from(&self, pos: &Pos, n: isize) -> Pos {
+    fn forward_n_from(&self, pos: &Pos, n: isize) -> Pos {
+        let (dx, dy) = self.delta();
+        (pos.0 + dx * n, pos.1 + dy * n)
+    }
+}
+
+impl Heading for Direction {
+    fn all() -> &'static [Self] {
+        &DIRECTIONS
+    }
+
+    fn delta(&self) -> Pos {
+        match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+            Direction::East => (1, 0),
+        }
+    }
+
+    fn to_char(&self) -> char {
         match self {
-            Direction::North => (pos.0, pos.1 - n),
-            Direction::South => (pos.0, pos.1 + n),
-            Direction::West => (pos.0 - n, pos.1),
-            Direction::East => (pos.0 + n, pos.1),
+            Direction::North => '^',
+            Direction::South => 'v',
+            Direction::West => '<',
+            Direction::East => '>',
         }
     }
 
-    pub fn turn_left(&self) -> Direction {
+    fn turn_left(&self) -> Direction {
         match self {
             Direction::North => Direction::West,
             Direction::South => Direction::East,
@@ -62,7 +115,7 @@ impl Direction {
         }
     }
 
-    pub fn turn_right(&self) -> Direction {
+    fn turn_right(&self) -> Direction {
         match self {
             Direction::North => Direction::East,
             Direction::South => Direction::West,
@@ -71,7 +124,7 @@ impl Direction {
         }
     }
 
-    pub fn invert(&self) -> Direction {
+    fn invert(&self) -> Direction {
         match self {
             Direction::North => Direction::South,
             Direction::South => Direction::North,
@@ -81,6 +134,97 @@ impl Direction {
     }
 }
 
+/// The orthogonal and diagonal directions, e.g. for word searches or 8-connected regions.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Direction8 {
+    #[default]
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+/// All 8 directions, clockwise from north.
+pub static DIRECTIONS8: [Direction8; 8] = [
+    Direction8::North,
+    Direction8::NorthEast,
+    Direction8::East,
+    Direction8::SouthEast,
+    Direction8::South,
+    Direction8::SouthWest,
+    Direction8::West,
+    Direction8::NorthWest,
+];
+
+impl Direction8 {
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        self.index() % 2 == 1
+    }
+}
+
+impl Heading for Direction8 {
+    fn all() -> &'static [Self] {
+        &DIRECTIONS8
+    }
+
+    fn delta(&self) -> Pos {
+        match self {
+            Direction8::North => (0, -1),
+            Direction8::NorthEast => (1, -1),
+            Direction8::East => (1, 0),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (0, 1),
+            Direction8::SouthWest => (-1, 1),
+            Direction8::West => (-1, 0),
+            Direction8::NorthWest => (-1, -1),
+        }
+    }
+
+    fn to_char(&self) -> char {
+        match self {
+            Direction8::North => '↑',
+            Direction8::NorthEast => '↗',
+            Direction8::East => '→',
+            Direction8::SouthEast => '↘',
+            Direction8::South => '↓',
+            Direction8::SouthWest => '↙',
+            Direction8::West => '←',
+            Direction8::NorthWest => '↖',
+        }
+    }
+
+    fn turn_left(&self) -> Direction8 {
+        DIRECTIONS8[(self.index() + 7) % 8]
+    }
+
+    fn turn_right(&self) -> Direction8 {
+        DIRECTIONS8[(self.index() + 1) % 8]
+    }
+
+    fn invert(&self) -> Direction8 {
+        DIRECTIONS8[(self.index() + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Self {
+        match dir {
+            Direction::North => Direction8::North,
+            Direction::South => Direction8::South,
+            Direction::West => Direction8::West,
+            Direction::East => Direction8::East,
+        }
+    }
+}
+
 impl ops::Add<&Pos> for Direction {
     type Output = Pos;
 
@@ -97,19 +241,82 @@ impl ops::Sub<&Pos> for Direction {
     }
 }
 
-pub fn flood_fill<F, G>(start: &Pos, mut on_each: F, mut is_successor: G)
+impl ops::Add<&Pos> for Direction8 {
+    type Output = Pos;
+
+    fn add(self, pos: &Pos) -> Self::Output {
+        self.forward_from(pos)
+    }
+}
+
+impl ops::Sub<&Pos> for Direction8 {
+    type Output = Pos;
+
+    fn sub(self, pos: &Pos) -> Self::Output {
+        self.invert().forward_from(pos)
+    }
+}
+
+/// Visits `start` and every position reachable through `is_successor`, stepping in the
+/// directions of `D`: [`Direction`] for 4-connected and [`Direction8`] for 8-connected areas.
+pub fn flood_fill<D, F, G>(start: &Pos, mut on_each: F, mut is_successor: G)
 where
+    D: Heading,
     F: FnMut(&Pos),
-    G: FnMut(&Pos, &Pos, &Direction) -> bool,
+    G: FnMut(&Pos, &Pos, &D) -> bool,
 {
     let mut queue = VecDeque::from([*start]);
     while let Some(pos) = queue.pop_front() {
         on_each(&pos);
-        for dir in DIRECTIONS {
+        for dir in D::all() {
             let pos2 = dir.forward_from(&pos);
-            if is_successor(&pos, &pos2, &dir) {
+            if is_successor(&pos, &pos2, dir) {
                 queue.push_back(pos2);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hashbrown::HashSet;
+
+    #[test]
+    fn test_direction8_turns() {
+        assert_eq!(Direction8::North.turn_right(), Direction8::NorthEast);
+        assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.invert(), Direction8::NorthEast);
+        assert!(Direction8::SouthEast.is_diagonal());
+        assert_eq!(Direction8::from(Direction::West), Direction8::West);
+    }
+
+    #[test]
+    fn test_deltas() {
+        assert_eq!(Direction8::from_delta(&(1, 1)), Some(Direction8::SouthEast));
+        assert_eq!(Direction::from_delta(&(1, 1)), None);
+        assert_eq!(Direction8::NorthWest.forward_n_from(&(5, 5), 2), (3, 3));
+        assert_eq!(Direction::East.backward_from(&(5, 5)), (4, 5));
+        for dir in DIRECTIONS {
+            assert_eq!(Direction::from_char(dir.to_char()), dir);
+        }
+    }
+
+    #[test]
+    fn test_flood_fill_connectivity() {
+        let cells: HashSet<Pos> = [(0, 0), (1, 1), (2, 2)].into_iter().collect();
+        let count = |diagonal: bool| {
+            let mut seen: HashSet<Pos> = [(0, 0)].into_iter().collect();
+            let mut is_successor = |pos2: &Pos| cells.contains(pos2) && seen.insert(*pos2);
+            let mut count = 0;
+            if diagonal {
+                flood_fill::<Direction8, _, _>(&(0, 0), |_| count += 1, |_, p, _| is_successor(p));
+            } else {
+                flood_fill::<Direction, _, _>(&(0, 0), |_| count += 1, |_, p, _| is_successor(p));
+            }
+            count
+        };
+        assert_eq!(count(false), 1);
+        assert_eq!(count(true), 3);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{Grid2D, Pos};
+use rayon::prelude::*;
+use std::collections::VecDeque;
+
+/// The number of steps from a start position to every reachable cell of a grid, e.g. the
+/// time at which a racer passes each cell of the track:
+///
+/// ```
+/// let moves = GridMoves::new(&walls).bounded(bounds);
+/// let field = DistanceField::bfs(&start, bounds, |pos| moves.neighbors(pos));
+/// let cheats = field.shortcuts(20, 100);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistanceField {
+    pub start: Pos,
+    distances: Grid2D<Option<usize>>,
+}
+
+impl DistanceField {
+    /// Runs a BFS from `start` over the cells from (0, 0) to `bounds`, moves leaving the
+    /// bounds are ignored.
+    pub fn bfs<FN, IN>(start: &Pos, bounds: Pos, mut neighbors: FN) -> Self
+    where
+        FN: FnMut(&Pos) -> IN,
+        IN: IntoIterator<Item = Pos>,
+    {
+        let (width, height) = ((bounds.0 + 1) as usize, (bounds.1 + 1) as usize);
+        let mut distances = Grid2D::new(width, height, None);
+        let mut to_see = VecDeque::new();
+        if let Some(cell) = distances.get_mut(start) {
+            *cell = Some(0);
+            to_see.push_back((*start, 0));
+        }
+        while let Some((pos, distance)) = to_see.pop_front() {
+            for pos2 in neighbors(&pos) {
+                if let Some(cell @ None) = distances.get_mut(&pos2) {
+                    *cell = Some(distance + 1);
+                    to_see.push_back((pos2, distance + 1));
+                }
+            }
+        }
+        Self {
+            start: *start,
+            distances,
+        }
+    }
+
+    /// The distance from the start, or None if `pos` can't be reached.
+    pub fn get(&self, pos: &Pos) -> Option<usize> {
+        self.distances.get(pos).copied().flatten()
+    }
+
+    /// The reachable cells with their distance from the start, row by row.
+    pub fn reachable(&self) -> impl Iterator<Item = (Pos, usize)> + '_ {
+        self.distances
+            .cells()
+            .filter_map(|(pos, distance)| distance.map(|d| (pos, d)))
+    }
+
+    /// The distance of the farthest reachable cell.
+    pub fn max(&self) -> Option<usize> {
+        self.reachable().map(|(_, distance)| distance).max()
+    }
+
+    /// Counts the pairs of reachable cells at most `max_jump` apart (Manhattan distance) for
+    /// which jumping from one to the other, ignoring walls, saves at least `min_save` steps
+    /// over the distances from the start.
+    pub fn shortcuts(&self, max_jump: usize, min_save: usize) -> usize {
+        let reachable: Vec<(Pos, usize)> = self.reachable().collect();
+        reachable
+            .par_iter()
+            .map(|(pos, from)| {
+                manhattan_ball(pos, max_jump)
+                    .filter(|(pos2, jump)| {
+                        self.get(pos2)
+                            .is_some_and(|to| to >= from + jump + min_save)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+}
+
+/// All positions within Manhattan distance `radius` of `center`, including the center
+/// itself, along with their distance to it.
+pub fn manhattan_ball(center: &Pos, radius: usize) -> impl Iterator<Item = (Pos, usize)> {
+    let (center, r) = (*center, radius as isize);
+    (-r..=r).flat_map(move |dy| {
+        let width = r - dy.abs();
+        (-width..=width).map(move |dx| {
+            let distance = (dx.abs() + dy.abs()) as usize;
+            ((center.0 + dx, center.1 + dy), distance)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid_of, GridMoves};
+    use aoc_parse::{parser, Parser};
+
+    #[test]
+    fn test_manhattan_ball() {
+        assert_eq!(
+            manhattan_ball(&(0, 0), 0).collect::<Vec<_>>(),
+            vec![((0, 0), 0)]
+        );
+        let ball: Vec<_> = manhattan_ball(&(5, -2), 3).collect();
+        assert_eq!(ball.len(), 25);
+        assert!(ball
+            .iter()
+            .all(|((x, y), d)| (x - 5).unsigned_abs() + (y + 2).unsigned_abs() == *d && *d <= 3));
+        assert_eq!(ball.iter().filter(|(_, d)| *d == 3).count(), 12);
+    }
+
+    #[test]
+    fn test_distance_field() {
+        // a track that winds around a wall.
+        let grid = parser!(grid_of(".#")).parse("...\n##.\n...\n").unwrap();
+        let moves = GridMoves::from_grid(&grid, '#');
+        let field = DistanceField::bfs(&(0, 0), grid.bounds, |pos| moves.neighbors(pos));
+        assert_eq!(field.get(&(0, 2)), Some(6));
+        assert_eq!(field.get(&(0, 1)), None);
+        assert_eq!(field.get(&(9, 9)), None);
+        assert_eq!(field.max(), Some(6));
+        assert_eq!(field.reachable().count(), 7);
+
+        // jumping the wall from (0, 0) to (0, 2) saves 4 steps, from (0, 0) or (1, 0) to
+        // (1, 2) or (0, 2) at least 2.
+        assert_eq!(field.shortcuts(2, 4), 1);
+        assert_eq!(field.shortcuts(2, 5), 0);
+        assert_eq!(field.shortcuts(3, 2), 4);
+    }
+}
@@ -0,0 +1,388 @@
+use crate::IdMap;
+use hashbrown::HashSet;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::ops::Range;
+
+/// A graph stored as sets of neighbors, with its nodes numbered through an [`IdMap`].
+/// The algorithms work on the ids, [`key`](Self::key) maps them back:
+///
+/// ```
+/// let mut network = AdjacencyGraph::undirected();
+/// for (a, b) in links {
+///     network.add_edge(&a, &b);
+/// }
+/// let password = network.maximum_clique().iter().map(|id| network.key(*id)).sorted().join(",");
+/// ```
+pub struct AdjacencyGraph<T: Hash + Eq> {
+    ids: IdMap<T>,
+    outgoing: Vec<HashSet<usize>>,
+    incoming: Vec<HashSet<usize>>,
+    directed: bool,
+}
+
+impl<T: Clone + Eq + Hash> AdjacencyGraph<T> {
+    pub fn undirected() -> Self {
+        Self {
+            ids: IdMap::new(),
+            outgoing: vec![],
+            incoming: vec![],
+            directed: false,
+        }
+    }
+
+    pub fn directed() -> Self {
+        Self {
+            directed: true,
+            ..Self::undirected()
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Adds a node if it is new, returning its id.
+    pub fn add_node(&mut self, node: &T) -> usize {
+        let id = self.ids.id(node);
+        if id == self.outgoing.len() {
+            self.outgoing.push(HashSet::new());
+            self.incoming.push(HashSet::new());
+        }
+        id
+    }
+
+    /// Adds an edge from `a` to `b`, and from `b` to `a` in undirected graphs.
+    pub fn add_edge(&mut self, a: &T, b: &T) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.outgoing[a].insert(b);
+        self.incoming[b].insert(a);
+        if !self.directed {
+            self.outgoing[b].insert(a);
+            self.incoming[a].insert(b);
+        }
+    }
+
+    pub fn id(&self, node: &T) -> Option<usize> {
+        self.ids.get(node)
+    }
+
+    pub fn key(&self, id: usize) -> &T {
+        self.ids.key(&id).unwrap()
+    }
+
+    pub fn ids(&self) -> Range<usize> {
+        0..self.outgoing.len()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.outgoing.len()
+    }
+
+    pub fn successors(&self, id: usize) -> &HashSet<usize> {
+        &self.outgoing[id]
+    }
+
+    pub fn predecessors(&self, id: usize) -> &HashSet<usize> {
+        &self.incoming[id]
+    }
+
+    pub fn contains_edge(&self, a: usize, b: usize) -> bool {
+        self.outgoing[a].contains(&b)
+    }
+
+    /// Successors and predecessors, i.e. the neighbors when ignoring edge directions.
+    fn neighbors(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        self.outgoing[id].union(&self.incoming[id]).copied()
+    }
+
+    /// Every set of three nodes of an undirected graph that are all connected to each other,
+    /// in increasing order.
+    pub fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        self.ids().flat_map(move |a| {
+            let above = move |n: &&usize| **n > a;
+            self.outgoing[a].iter().filter(above).flat_map(move |b| {
+                self.outgoing[*b]
+                    .iter()
+                    .filter(move |c| *c > b && self.outgoing[a].contains(*c))
+                    .map(move |c| [a, *b, *c])
+            })
+        })
+    }
+
+    /// All cliques that can't be extended by another node, found with Bron–Kerbosch.
+    pub fn maximal_cliques(&self) -> Vec<HashSet<usize>> {
+        let mut cliques = vec![];
+        self.bron_kerbosch(
+            HashSet::new(),
+            self.ids().collect(),
+            HashSet::new(),
+            &mut cliques,
+        );
+        cliques
+    }
+
+    /// A largest clique, or an empty set for an empty graph.
+    pub fn maximum_clique(&self) -> HashSet<usize> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by_key(HashSet::len)
+            .unwrap_or_default()
+    }
+
+    fn bron_kerbosch(
+        &self,
+        r: HashSet<usize>,
+        mut p: HashSet<usize>,
+        mut x: HashSet<usize>,
+        cliques: &mut Vec<HashSet<usize>>,
+    ) {
+        // pivot on the node with the most candidates as neighbors, to skip the most branches.
+        let Some(pivot) = p
+            .union(&x)
+            .max_by_key(|n| p.intersection(&self.outgoing[**n]).count())
+            .copied()
+        else {
+            cliques.push(r);
+            return;
+        };
+
+        let candidates: Vec<usize> = p.difference(&self.outgoing[pivot]).copied().collect();
+        for v in candidates {
+            let neighbors = &self.outgoing[v];
+            let mut new_r = r.clone();
+            new_r.insert(v);
+            let new_p = p.intersection(neighbors).copied().collect();
+            let new_x = x.intersection(neighbors).copied().collect();
+            self.bron_kerbosch(new_r, new_p, new_x, cliques);
+
+            p.remove(&v);
+            x.insert(v);
+        }
+    }
+
+    /// The connected components, ignoring edge directions.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.node_count()];
+        let mut components = vec![];
+        for start in self.ids() {
+            if seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut component = vec![];
+            let mut to_see = VecDeque::from([start]);
+            while let Some(node) = to_see.pop_front() {
+                component.push(node);
+                for next in self.neighbors(node) {
+                    if !std::mem::replace(&mut seen[next], true) {
+                        to_see.push_back(next);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// The strongly connected components (Kosaraju), in topological order of the graph
+    /// they form when each is contracted to one node.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // order the nodes by when a DFS finishes them, without recursion.
+        let mut finished = vec![];
+        let mut seen = vec![false; self.node_count()];
+        for start in self.ids() {
+            if std::mem::replace(&mut seen[start], true) {
+                continue;
+            }
+            let mut stack = vec![(start, self.outgoing[start].iter())];
+            while let Some((node, successors)) = stack.last_mut() {
+                if let Some(next) = successors.find(|n| !seen[**n]) {
+                    seen[*next] = true;
+                    stack.push((*next, self.outgoing[*next].iter()));
+                } else {
+                    finished.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+
+        // the last node to finish is in a source component, collect it against the edges.
+        let mut component_of = vec![None; self.node_count()];
+        let mut components: Vec<Vec<usize>> = vec![];
+        for start in finished.into_iter().rev() {
+            if component_of[start].is_some() {
+                continue;
+            }
+            let index = components.len();
+            component_of[start] = Some(index);
+            let mut component = vec![];
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                component.push(node);
+                for prev in &self.incoming[node] {
+                    if component_of[*prev].is_none() {
+                        component_of[*prev] = Some(index);
+                        stack.push(*prev);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Orders the nodes so that every edge points forward (Kahn). If the graph has a cycle,
+    /// returns the nodes of one of them in order instead.
+    pub fn topological_sort(&self) -> Result<Vec<usize>, Vec<usize>> {
+        let mut in_degree: Vec<usize> = self.incoming.iter().map(HashSet::len).collect();
+        let mut ready: VecDeque<usize> = self.ids().filter(|n| in_degree[*n] == 0).collect();
+        let mut order = vec![];
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for next in &self.outgoing[node] {
+                in_degree[*next] -= 1;
+                if in_degree[*next] == 0 {
+                    ready.push_back(*next);
+                }
+            }
+        }
+        if order.len() == self.node_count() {
+            return Ok(order);
+        }
+
+        // every node left has a predecessor that is left, walk back until one repeats.
+        let mut node = self.ids().find(|n| in_degree[*n] > 0).unwrap();
+        let mut walked = vec![];
+        while !walked.contains(&node) {
+            walked.push(node);
+            node = *self.incoming[node]
+                .iter()
+                .find(|prev| in_degree[**prev] > 0)
+                .unwrap();
+        }
+        let start = walked.iter().position(|n| *n == node).unwrap();
+        let mut cycle = walked.split_off(start);
+        cycle.reverse();
+        Err(cycle)
+    }
+
+    /// Splits the nodes into two sides with every edge going between them, ignoring edge
+    /// directions, or None if the graph has an odd cycle.
+    pub fn bipartition(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        let mut side = vec![None; self.node_count()];
+        for start in self.ids() {
+            if side[start].is_some() {
+                continue;
+            }
+            side[start] = Some(false);
+            let mut to_see = VecDeque::from([start]);
+            while let Some(node) = to_see.pop_front() {
+                for next in self.neighbors(node) {
+                    match side[next] {
+                        None => {
+                            side[next] = side[node].map(|s| !s);
+                            to_see.push_back(next);
+                        }
+                        s if s == side[node] => return None,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Some(self.ids().partition(|n| side[*n] == Some(false)))
+    }
+
+    pub fn is_bipartite(&self) -> bool {
+        self.bipartition().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    fn undirected(edges: &[(&str, &str)]) -> AdjacencyGraph<String> {
+        let mut graph = AdjacencyGraph::undirected();
+        for (a, b) in edges {
+            graph.add_edge(&a.to_string(), &b.to_string());
+        }
+        graph
+    }
+
+    fn directed(edges: &[(u8, u8)]) -> AdjacencyGraph<u8> {
+        let mut graph = AdjacencyGraph::directed();
+        for (a, b) in edges {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_cliques() {
+        // a square with one diagonal, plus a separate edge.
+        let graph = undirected(&[
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "a"),
+            ("a", "c"),
+            ("e", "f"),
+        ]);
+        assert_eq!(graph.triangles().count(), 2);
+        assert_eq!(graph.maximal_cliques().len(), 3);
+        let clique = graph.maximum_clique();
+        assert_eq!(clique.len(), 3);
+        assert!(clique.contains(&graph.id(&"a".to_string()).unwrap()));
+        assert_eq!(graph.components().len(), 2);
+        assert!(!graph.is_bipartite());
+    }
+
+    #[test]
+    fn test_bipartition() {
+        let graph = undirected(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "a")]);
+        let (left, right) = graph.bipartition().unwrap();
+        assert_eq!((left.len(), right.len()), (2, 2));
+        assert!(!graph.contains_edge(left[0], left[1]));
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let graph = directed(&[(1, 2), (1, 3), (3, 2), (2, 4)]);
+        let order: Vec<u8> = graph
+            .topological_sort()
+            .unwrap()
+            .into_iter()
+            .map(|id| *graph.key(id))
+            .collect();
+        assert_eq!(order, vec![1, 3, 2, 4]);
+
+        let cyclic = directed(&[(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)]);
+        let mut cycle: Vec<u8> = cyclic
+            .topological_sort()
+            .unwrap_err()
+            .into_iter()
+            .map(|id| *cyclic.key(id))
+            .collect();
+        let first = cycle.iter().position(|n| *n == 2).unwrap();
+        cycle.rotate_left(first);
+        assert_eq!(cycle, vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let graph = directed(&[(1, 2), (2, 3), (3, 2), (3, 4), (5, 4)]);
+        let components: Vec<Vec<u8>> = graph
+            .strongly_connected_components()
+            .into_iter()
+            .map(|c| c.into_iter().map(|id| *graph.key(id)).sorted().collect())
+            .collect();
+        assert_eq!(components.len(), 4);
+        assert!(components.contains(&vec![2, 3]));
+        let position = |n: u8| components.iter().position(|c| c.contains(&n)).unwrap();
+        assert!(position(1) < position(2) && position(2) < position(4));
+        assert!(position(5) < position(4));
+        assert_eq!(graph.components().len(), 1);
+    }
+}
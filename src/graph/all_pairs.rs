@@ -0,0 +1,253 @@
+use super::LeastCost;
+use num::Num;
+use petgraph::algo::NegativeCycle;
+use petgraph::graph::{IndexType, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::{EdgeType, Graph};
+use std::collections::BinaryHeap;
+
+/// The shortest distance between every pair of nodes of a graph, along with the next hop
+/// to follow on a shortest path:
+///
+/// ```
+/// let matrix = floyd_warshall(&graph).unwrap();
+/// let cost = matrix.distance(a, b);
+/// let route = matrix.path(a, b);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistanceMatrix<E, Ix: IndexType> {
+    size: usize,
+    distances: Vec<Option<E>>,
+    next: Vec<Option<NodeIndex<Ix>>>,
+}
+
+impl<E: Copy, Ix: IndexType> DistanceMatrix<E, Ix> {
+    fn new(size: usize) -> Self {
+        Self {
+            size,
+            distances: vec![None; size * size],
+            next: vec![None; size * size],
+        }
+    }
+
+    fn offset(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> usize {
+        a.index() * self.size + b.index()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.size
+    }
+
+    /// The length of a shortest path from `a` to `b`, or None if there is none.
+    pub fn distance(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<E> {
+        self.distances[self.offset(a, b)]
+    }
+
+    /// The nodes of a shortest path from `a` to `b`, including both.
+    pub fn path(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<Vec<NodeIndex<Ix>>> {
+        let mut path = vec![a];
+        while *path.last().unwrap() != b {
+            path.push(self.next[self.offset(*path.last().unwrap(), b)]?);
+        }
+        Some(path)
+    }
+
+    /// All pairs of nodes connected by a path, with their distance.
+    pub fn pairs(&self) -> impl Iterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>, E)> + '_ {
+        let size = self.size;
+        self.distances.iter().enumerate().filter_map(move |(i, d)| {
+            d.map(|d| (NodeIndex::new(i / size), NodeIndex::new(i % size), d))
+        })
+    }
+}
+
+/// The outgoing edges of every node, both ways for undirected graphs.
+fn adjacency<N, E, Ty, Ix>(graph: &Graph<N, E, Ty, Ix>) -> Vec<Vec<(NodeIndex<Ix>, E)>>
+where
+    E: Copy,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    let mut adjacency = vec![vec![]; graph.node_count()];
+    for edge in graph.edge_references() {
+        let (source, target, weight) = (edge.source(), edge.target(), *edge.weight());
+        adjacency[source.index()].push((target, weight));
+        if !graph.is_directed() && source != target {
+            adjacency[target.index()].push((source, weight));
+        }
+    }
+    adjacency
+}
+
+/// Floyd–Warshall on a dense matrix, for small or dense graphs. Parallel edges are
+/// reduced to the cheapest one. Fails if the graph has a cycle of negative total weight.
+pub fn floyd_warshall<N, E, Ty, Ix>(
+    graph: &Graph<N, E, Ty, Ix>,
+) -> Result<DistanceMatrix<E, Ix>, NegativeCycle>
+where
+    E: Num + PartialOrd + Copy,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    let size = graph.node_count();
+    let mut matrix = DistanceMatrix::new(size);
+    for node in graph.node_indices() {
+        let i = matrix.offset(node, node);
+        matrix.distances[i] = Some(E::zero());
+        matrix.next[i] = Some(node);
+    }
+    for (source, edges) in adjacency(graph).into_iter().enumerate() {
+        for (target, weight) in edges {
+            let i = matrix.offset(NodeIndex::new(source), target);
+            if matrix.distances[i].map_or(true, |d| weight < d) {
+                matrix.distances[i] = Some(weight);
+                matrix.next[i] = Some(target);
+            }
+        }
+    }
+
+    for k in 0..size {
+        for i in 0..size {
+            let Some(ik) = matrix.distances[i * size + k] else {
+                continue;
+            };
+            for j in 0..size {
+                let Some(kj) = matrix.distances[k * size + j] else {
+                    continue;
+                };
+                if matrix.distances[i * size + j].map_or(true, |ij| ik + kj < ij) {
+                    matrix.distances[i * size + j] = Some(ik + kj);
+                    matrix.next[i * size + j] = matrix.next[i * size + k];
+                }
+            }
+        }
+    }
+
+    if (0..size).any(|i| matrix.distances[i * size + i].is_some_and(|d| d < E::zero())) {
+        return Err(NegativeCycle(()));
+    }
+    Ok(matrix)
+}
+
+/// Johnson's algorithm, which is faster than [`floyd_warshall`] on large sparse graphs: it
+/// reweights the edges with Bellman-Ford to make them non-negative, then runs Dijkstra from
+/// every node. Fails if the graph has a cycle of negative total weight.
+pub fn johnson<N, E, Ty, Ix>(
+    graph: &Graph<N, E, Ty, Ix>,
+) -> Result<DistanceMatrix<E, Ix>, NegativeCycle>
+where
+    E: Num + Ord + Copy,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    let size = graph.node_count();
+    let adjacency = adjacency(graph);
+
+    // Bellman-Ford from a virtual node with a free edge to every node.
+    let mut potential = vec![E::zero(); size];
+    for round in 0..=size {
+        let mut changed = false;
+        for (source, edges) in adjacency.iter().enumerate() {
+            for (target, weight) in edges {
+                if potential[source] + *weight < potential[target.index()] {
+                    potential[target.index()] = potential[source] + *weight;
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            break;
+        }
+        if round == size {
+            return Err(NegativeCycle(()));
+        }
+    }
+
+    let mut matrix = DistanceMatrix::new(size);
+    for start in graph.node_indices() {
+        // the first hop on the way to each node, found in the order nodes are settled.
+        let mut first_hop: Vec<Option<NodeIndex<Ix>>> = vec![None; size];
+        let mut reweighted: Vec<Option<E>> = vec![None; size];
+        let mut settled = vec![false; size];
+        let mut to_see = BinaryHeap::new();
+        reweighted[start.index()] = Some(E::zero());
+        first_hop[start.index()] = Some(start);
+        to_see.push(LeastCost {
+            node: start,
+            cost: E::zero(),
+        });
+
+        while let Some(LeastCost { node, cost }) = to_see.pop() {
+            if std::mem::replace(&mut settled[node.index()], true) {
+                continue;
+            }
+            for (target, weight) in &adjacency[node.index()] {
+                let new_cost = cost + *weight + potential[node.index()] - potential[target.index()];
+                if reweighted[target.index()].map_or(true, |old| new_cost < old) {
+                    reweighted[target.index()] = Some(new_cost);
+                    first_hop[target.index()] = if node == start {
+                        Some(*target)
+                    } else {
+                        first_hop[node.index()]
+                    };
+                    to_see.push(LeastCost {
+                        node: *target,
+                        cost: new_cost,
+                    });
+                }
+            }
+        }
+
+        for target in graph.node_indices() {
+            let i = matrix.offset(start, target);
+            matrix.distances[i] = reweighted[target.index()]
+                .map(|d| d - potential[start.index()] + potential[target.index()]);
+            matrix.next[i] = first_hop[target.index()];
+        }
+    }
+    Ok(matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::graph::{DiGraph, UnGraph};
+
+    fn sample() -> DiGraph<(), i32> {
+        // 0 -> 1 has a cheaper parallel edge, 2 -> 1 is negative.
+        DiGraph::from_edges([(0, 1, 9), (0, 1, 4), (0, 2, 3), (2, 1, -2), (1, 3, 1)])
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let n = NodeIndex::new;
+        let matrix = floyd_warshall(&sample()).unwrap();
+        assert_eq!(matrix.distance(n(0), n(1)), Some(1));
+        assert_eq!(matrix.distance(n(0), n(3)), Some(2));
+        assert_eq!(matrix.distance(n(3), n(0)), None);
+        assert_eq!(matrix.path(n(0), n(3)), Some(vec![n(0), n(2), n(1), n(3)]));
+        assert_eq!(matrix.path(n(2), n(2)), Some(vec![n(2)]));
+        assert_eq!(matrix.path(n(3), n(0)), None);
+        assert_eq!(matrix.pairs().count(), 4 + 3 + 2 + 1);
+    }
+
+    #[test]
+    fn test_johnson_matches_floyd_warshall() {
+        assert_eq!(johnson(&sample()), floyd_warshall(&sample()));
+
+        let grid = UnGraph::<(), u32>::from_edges([(0, 1, 2), (1, 2, 2), (0, 2, 5), (2, 3, 1)]);
+        let matrix = johnson(&grid).unwrap();
+        assert_eq!(matrix, floyd_warshall(&grid).unwrap());
+        assert_eq!(
+            matrix.path(NodeIndex::new(3), NodeIndex::new(0)),
+            Some(vec![3, 2, 1, 0].into_iter().map(NodeIndex::new).collect())
+        );
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let graph = DiGraph::<(), i32>::from_edges([(0, 1, 1), (1, 2, -3), (2, 0, 1)]);
+        assert_eq!(floyd_warshall(&graph), Err(NegativeCycle(())));
+        assert_eq!(johnson(&graph), Err(NegativeCycle(())));
+    }
+}
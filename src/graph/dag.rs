@@ -0,0 +1,307 @@
+use super::LeastCost;
+use hashbrown::hash_map::Entry::{Occupied, Vacant};
+use hashbrown::{HashMap, HashSet};
+use num::{BigUint, One, Zero};
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+/// All shortest paths from a start node to the cheapest goal nodes, as found by
+/// [`dijkstra_multi`]. The paths form a DAG in which every node keeps all the parents it can
+/// be reached from at its lowest cost:
+///
+/// ```
+/// let paths = dijkstra_multi(&start, successors, |n| n.0 == end).unwrap();
+/// let seats = paths.nodes().into_iter().unique_by(|n| n.0).count();
+/// let routes = paths.count();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShortestPaths<N: Eq + Hash, C> {
+    pub start: N,
+    /// The goal nodes reached at the lowest cost, in the order they were settled.
+    pub goals: Vec<N>,
+    pub cost: C,
+    /// The parents and cost of every settled node, the start node has no parents.
+    pub parents: HashMap<N, (Vec<N>, C)>,
+}
+
+impl<N: Eq + Hash + Clone, C> ShortestPaths<N, C> {
+    /// The nodes lying on at least one of the shortest paths.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+        let mut stack = self.goals.clone();
+        while let Some(node) = stack.pop() {
+            if nodes.insert(node.clone()) {
+                stack.extend(self.parents[&node].0.iter().cloned());
+            }
+        }
+        nodes
+    }
+
+    /// The number of distinct shortest paths, which can grow exponentially with the size of
+    /// the graph.
+    pub fn count(&self) -> BigUint {
+        // the number of paths to a node is the sum over its parents, counted after all of
+        // them without recursing so deep graphs don't overflow the stack.
+        let mut counts: HashMap<&N, BigUint> = HashMap::new();
+        let mut stack: Vec<&N> = self.goals.iter().collect();
+        while let Some(&node) = stack.last() {
+            if counts.contains_key(node) {
+                stack.pop();
+                continue;
+            }
+            let parents = &self.parents[node].0;
+            let missing: Vec<&N> = parents.iter().filter(|p| !counts.contains_key(p)).collect();
+            if missing.is_empty() {
+                let count = if parents.is_empty() {
+                    BigUint::one()
+                } else {
+                    parents.iter().map(|p| &counts[p]).sum()
+                };
+                counts.insert(node, count);
+                stack.pop();
+            } else {
+                stack.extend(missing);
+            }
+        }
+        self.goals.iter().map(|goal| &counts[goal]).sum()
+    }
+
+    /// Iterates over every shortest path, from the start to one of the goals. There can be a
+    /// lot of them, check [`count`](Self::count) first.
+    pub fn paths(&self) -> Paths<'_, N, C> {
+        Paths {
+            dag: self,
+            goals: self.goals.iter(),
+            stack: vec![],
+        }
+    }
+}
+
+/// The iterator returned by [`ShortestPaths::paths`].
+pub struct Paths<'a, N: Eq + Hash, C> {
+    dag: &'a ShortestPaths<N, C>,
+    goals: std::slice::Iter<'a, N>,
+    /// The path from the current goal back towards the start, with the index of the next
+    /// parent to follow from each node.
+    stack: Vec<(&'a N, usize)>,
+}
+
+impl<N: Eq + Hash + Clone, C> Iterator for Paths<'_, N, C> {
+    type Item = Vec<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some((node, next)) = self.stack.last_mut() else {
+                self.stack.push((self.goals.next()?, 0));
+                continue;
+            };
+            let parents = &self.dag.parents[*node].0;
+            if parents.is_empty() {
+                let path = self.stack.iter().rev().map(|(n, _)| (*n).clone()).collect();
+                self.stack.pop();
+                return Some(path);
+            }
+            if let Some(parent) = parents.get(*next) {
+                *next += 1;
+                self.stack.push((parent, 0));
+            } else {
+                self.stack.pop();
+            }
+        }
+    }
+}
+
+/// Special-purpose alternative version of dijkstra which finds ALL shortest paths from
+/// `start` to the first node where `stop` holds. Other goal nodes at the same cost are
+/// ignored, use [`dijkstra_multi_all`] to include them. Returns None if no goal is reachable.
+pub fn dijkstra_multi<N, C, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    stop: FS,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    shortest_paths(start, successors, stop, false)
+}
+
+/// Like [`dijkstra_multi`], but keeps going until every node at the cost of the first goal is
+/// settled. This finds all goals at that cost, e.g. the end of a maze entered from any
+/// direction, and all parents of nodes reached through edges that cost nothing.
+pub fn dijkstra_multi_all<N, C, FN, IN, FS>(
+    start: &N,
+    successors: FN,
+    stop: FS,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    shortest_paths(start, successors, stop, true)
+}
+
+fn shortest_paths<N, C, FN, IN, FS>(
+    start: &N,
+    mut successors: FN,
+    mut stop: FS,
+    settle_all: bool,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut to_see = BinaryHeap::new();
+    to_see.push(LeastCost {
+        node: start.clone(),
+        cost: C::zero(),
+    });
+    let mut parents = HashMap::new();
+    parents.insert(start.clone(), (vec![], C::zero()));
+    let mut settled = HashSet::new();
+    let mut goals = vec![];
+    let mut goal_cost = None;
+
+    while let Some(LeastCost { node, cost }) = to_see.pop() {
+        if goal_cost.is_some_and(|goal_cost| cost > goal_cost) {
+            break;
+        }
+        // a node is queued again whenever its cost improves, only expand it once.
+        if !settled.insert(node.clone()) {
+            continue;
+        }
+        if stop(&node) {
+            goals.push(node);
+            goal_cost = Some(cost);
+            if settle_all {
+                continue;
+            }
+            break;
+        }
+        for (successor, move_cost) in successors(&node) {
+            let new_cost = cost + move_cost;
+            match parents.entry(successor.clone()) {
+                Vacant(e) => {
+                    e.insert((vec![node.clone()], new_cost));
+                }
+                Occupied(mut e) => {
+                    let old_cost = e.get().1;
+                    if new_cost < old_cost {
+                        e.insert((vec![node.clone()], new_cost));
+                    } else {
+                        // a settled node comes before this one, keeping it as a parent
+                        // would close a cycle over edges that cost nothing.
+                        if new_cost == old_cost && !settled.contains(&successor) {
+                            // THIS IS WHERE WE DEVIATE FROM DIJKSTRA!
+                            // Normally we'd skip this case, but here we're going
+                            // to keep track of all parents with the shortest cost
+                            e.get_mut().0.push(node.clone());
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            to_see.push(LeastCost {
+                node: successor,
+                cost: new_cost,
+            });
+        }
+    }
+
+    let cost = goal_cost?;
+    // nodes that were reached but never settled may be missing parents.
+    parents.retain(|node, _| settled.contains(node));
+    Some(ShortestPaths {
+        start: start.clone(),
+        goals,
+        cost,
+        parents,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a diamond of diamonds: 0 -> {1, 2} -> 3 -> {4, 5} -> 6, with a costlier shortcut.
+    fn edges(n: &u8) -> Vec<(u8, usize)> {
+        match n {
+            0 => vec![(1, 1), (2, 1), (6, 5)],
+            1 | 2 => vec![(3, 1)],
+            3 => vec![(4, 1), (5, 1)],
+            4 | 5 => vec![(6, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let paths = dijkstra_multi(&0, edges, |n| *n == 6).unwrap();
+        assert_eq!(paths.cost, 4);
+        assert_eq!(paths.goals, vec![6]);
+        assert_eq!(paths.nodes().len(), 7);
+        assert_eq!(paths.count(), BigUint::from(4u8));
+        let mut all: Vec<Vec<u8>> = paths.paths().collect();
+        all.sort();
+        assert_eq!(
+            all,
+            vec![
+                vec![0, 1, 3, 4, 6],
+                vec![0, 1, 3, 5, 6],
+                vec![0, 2, 3, 4, 6],
+                vec![0, 2, 3, 5, 6]
+            ]
+        );
+        assert_eq!(dijkstra_multi(&0, edges, |n| *n == 9), None);
+    }
+
+    #[test]
+    fn test_several_goals() {
+        let first = dijkstra_multi(&0, edges, |n| *n == 4 || *n == 5).unwrap();
+        assert_eq!(first.goals.len(), 1);
+        let all = dijkstra_multi_all(&0, edges, |n| *n == 4 || *n == 5).unwrap();
+        assert_eq!(all.cost, 3);
+        assert_eq!(all.goals.len(), 2);
+        assert_eq!(all.count(), BigUint::from(4u8));
+        assert_eq!(all.paths().count(), 4);
+        assert!(!all.nodes().contains(&6));
+    }
+
+    #[test]
+    fn test_zero_cost_cycle() {
+        let edges = |n: &u8| match n {
+            0 => vec![(1, 1)],
+            1 => vec![(2, 0)],
+            2 => vec![(1, 0), (3, 1)],
+            _ => vec![],
+        };
+        let paths = dijkstra_multi_all(&0, edges, |n| *n == 3).unwrap();
+        assert_eq!(paths.cost, 2);
+        assert_eq!(paths.count(), BigUint::one());
+        assert_eq!(paths.paths().count(), 1);
+        assert_eq!(paths.paths().next(), Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_count_overflows_u64() {
+        // a chain of 80 diamonds has 2^80 shortest paths.
+        let chain = |n: &u32| match n % 3 {
+            0 if *n < 240 => vec![(n + 1, 1), (n + 2, 1)],
+            1 => vec![(n + 2, 1)],
+            2 => vec![(n + 1, 1)],
+            _ => vec![],
+        };
+        let paths = dijkstra_multi(&0, chain, |n| *n == 240).unwrap();
+        assert_eq!(paths.count(), BigUint::one() << 80);
+    }
+}
@@ -0,0 +1,254 @@
+use petgraph::dot::Dot;
+use petgraph::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef,
+};
+use std::collections::VecDeque;
+use std::ffi::OsStr;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const SPACING: usize = 80;
+const RADIUS: usize = 20;
+
+/// Writes a graph to a file, picking the format from its extension: `.dot` and `.svg` are
+/// written directly (see [`write_dot`] and [`write_svg`]), other formats like `.png` or
+/// `.pdf` are rendered by piping the DOT text through Graphviz's `dot` command.
+pub fn write_graph<G>(graph: G, filename: &str) -> io::Result<()>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G::EdgeWeight: Display,
+    G::NodeWeight: Display,
+{
+    let ext = Path::new(filename)
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or_default();
+
+    match ext {
+        "dot" | "gv" => write_dot(graph, BufWriter::new(File::create(filename)?)),
+        "svg" => write_svg(graph, BufWriter::new(File::create(filename)?)),
+        _ => {
+            let mut child = Command::new("dot")
+                .arg(format!("-T{}", ext))
+                .arg("-o")
+                .arg(filename)
+                .stdin(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()?;
+            // dropping stdin closes it, so dot knows the graph is complete. The write fails
+            // if dot exits early, wait for it anyway to reap it and keep its error output.
+            let written = write_dot(graph, child.stdin.take().unwrap());
+            let output = child.wait_with_output()?;
+            if !output.status.success() {
+                return Err(io::Error::other(format!(
+                    "dot command failed with output:\n{}",
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+            written
+        }
+    }
+}
+
+/// Writes a graph in Graphviz's DOT format.
+pub fn write_dot<G, W>(graph: G, mut writer: W) -> io::Result<()>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G::EdgeWeight: Display,
+    G::NodeWeight: Display,
+    W: Write,
+{
+    write!(writer, "{}", Dot::with_config(graph, &[]))?;
+    writer.flush()
+}
+
+/// Draws a graph as SVG without any external tools. Nodes are laid out in layers by their
+/// distance from the nodes without incoming edges (or from the first node of every
+/// component if there are none), which suits circuits and other mostly acyclic graphs.
+pub fn write_svg<G, W>(graph: G, mut writer: W) -> io::Result<()>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G::EdgeWeight: Display,
+    G::NodeWeight: Display,
+    W: Write,
+{
+    let layers = layers(graph);
+    let widest = layers.iter().map(Vec::len).max().unwrap_or(0);
+    let (width, height) = (widest * SPACING, layers.len() * SPACING);
+
+    let mut position = vec![(0, 0); graph.node_bound()];
+    for (y, layer) in layers.iter().enumerate() {
+        // center the layer by shifting it half the missing width.
+        let indent = (widest - layer.len()) * SPACING / 2;
+        for (x, node) in layer.iter().enumerate() {
+            position[*node] = (
+                indent + x * SPACING + SPACING / 2,
+                y * SPACING + SPACING / 2,
+            );
+        }
+    }
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="12">"#
+    )?;
+    writeln!(
+        writer,
+        r#"<defs><marker id="arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto"><path d="M0,0 L10,5 L0,10 z"/></marker></defs>"#
+    )?;
+
+    let arrow = if graph.is_directed() {
+        r#" marker-end="url(#arrow)""#
+    } else {
+        ""
+    };
+    for edge in graph.edge_references() {
+        let from = position[graph.to_index(edge.source())];
+        let to = position[graph.to_index(edge.target())];
+        let (x1, y1, x2, y2) = shorten(from, to);
+        writeln!(
+            writer,
+            r#"<line x1="{x1:.1}" y1="{y1:.1}" x2="{x2:.1}" y2="{y2:.1}" stroke="black"{arrow}/>"#
+        )?;
+        let label = escape(&edge.weight().to_string());
+        if !label.is_empty() {
+            let (x, y) = ((from.0 + to.0) / 2, (from.1 + to.1) / 2);
+            writeln!(
+                writer,
+                r#"<text x="{x}" y="{y}" text-anchor="middle" fill="gray">{label}</text>"#
+            )?;
+        }
+    }
+
+    for node in graph.node_references() {
+        let (x, y) = position[graph.to_index(node.id())];
+        let label = escape(&node.weight().to_string());
+        writeln!(
+            writer,
+            r#"<circle cx="{x}" cy="{y}" r="{RADIUS}" fill="white" stroke="black"/>"#
+        )?;
+        writeln!(
+            writer,
+            r#"<text x="{x}" y="{y}" text-anchor="middle" dominant-baseline="central">{label}</text>"#
+        )?;
+    }
+
+    writeln!(writer, "</svg>")?;
+    writer.flush()
+}
+
+/// Groups the node indices by their BFS depth from the roots of the graph.
+fn layers<G>(graph: G) -> Vec<Vec<usize>>
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+{
+    let mut adjacent = vec![vec![]; graph.node_bound()];
+    let mut incoming = vec![0; graph.node_bound()];
+    for edge in graph.edge_references() {
+        let (source, target) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        adjacent[source].push(target);
+        if graph.is_directed() {
+            incoming[target] += 1;
+        } else {
+            adjacent[target].push(source);
+        }
+    }
+
+    let nodes: Vec<usize> = graph
+        .node_references()
+        .map(|node| graph.to_index(node.id()))
+        .collect();
+    let roots = nodes.iter().filter(|n| incoming[**n] == 0).chain(&nodes);
+
+    let mut depth = vec![None; graph.node_bound()];
+    let mut layers: Vec<Vec<usize>> = vec![];
+    for root in roots {
+        if depth[*root].is_some() {
+            continue;
+        }
+        depth[*root] = Some(0);
+        let mut to_see = VecDeque::from([*root]);
+        while let Some(node) = to_see.pop_front() {
+            let d = depth[node].unwrap();
+            if layers.len() <= d {
+                layers.push(vec![]);
+            }
+            layers[d].push(node);
+            for next in &adjacent[node] {
+                if depth[*next].is_none() {
+                    depth[*next] = Some(d + 1);
+                    to_see.push_back(*next);
+                }
+            }
+        }
+    }
+    layers
+}
+
+/// The line between two node centers, cut off at the nodes' circles.
+fn shorten(from: (usize, usize), to: (usize, usize)) -> (f64, f64, f64, f64) {
+    let (x1, y1, x2, y2) = (from.0 as f64, from.1 as f64, to.0 as f64, to.1 as f64);
+    let length = (x2 - x1).hypot(y2 - y1);
+    if length <= 2.0 * RADIUS as f64 {
+        return (x1, y1, x2, y2);
+    }
+    let (dx, dy) = (
+        (x2 - x1) / length * RADIUS as f64,
+        (y2 - y1) / length * RADIUS as f64,
+    );
+    (x1 + dx, y1 + dy, x2 - dx, y2 - dy)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use petgraph::graph::{DiGraph, UnGraph};
+
+    #[test]
+    fn test_write_dot() {
+        let mut graph = DiGraph::<&str, u8>::new();
+        let (a, b) = (graph.add_node("x00"), graph.add_node("z00"));
+        graph.add_edge(a, b, 1);
+        let mut dot = vec![];
+        write_dot(&graph, &mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("digraph {"));
+        assert!(dot.contains("0 -> 1 [ label = \"1\" ]"));
+    }
+
+    #[test]
+    fn test_layers() {
+        // a diamond below a root, a cycle without roots and two undirected components.
+        let graph = DiGraph::<u8, u8>::from_edges([(0, 1, 0), (0, 2, 0), (1, 3, 0), (2, 3, 0)]);
+        assert_eq!(layers(&graph), vec![vec![0], vec![1, 2], vec![3]]);
+        let cycle = DiGraph::<u8, u8>::from_edges([(0, 1, 0), (1, 2, 0), (2, 0, 0)]);
+        assert_eq!(layers(&cycle), vec![vec![0], vec![1], vec![2]]);
+        let network = UnGraph::<u8, u8>::from_edges([(1, 0, 0), (2, 1, 0), (3, 4, 0)]);
+        assert_eq!(layers(&network), vec![vec![0, 3], vec![1, 4], vec![2]]);
+    }
+
+    #[test]
+    fn test_write_svg() {
+        let mut graph = DiGraph::<&str, &str>::new();
+        let (a, b) = (graph.add_node("x<y"), graph.add_node("z"));
+        graph.add_edge(a, b, "AND");
+        let mut svg = vec![];
+        write_svg(&graph, &mut svg).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), 2);
+        assert!(svg.contains("x&lt;y"));
+        assert!(svg.contains("marker-end") && svg.contains(">AND</text>"));
+    }
+}
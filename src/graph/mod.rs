@@ -0,0 +1,38 @@
+use std::cmp::Ordering;
+
+mod adjacency;
+mod all_pairs;
+mod dag;
+mod export;
+mod search;
+
+pub use adjacency::*;
+pub use all_pairs::*;
+pub use dag::*;
+pub use export::*;
+pub use search::*;
+
+struct LeastCost<N, K> {
+    node: N,
+    cost: K,
+}
+
+impl<N, K: PartialEq> PartialEq for LeastCost<N, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl<N, K: PartialEq> Eq for LeastCost<N, K> {}
+
+impl<N, K: Ord> PartialOrd for LeastCost<N, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, K: Ord> Ord for LeastCost<N, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+    }
+}
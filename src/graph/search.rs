@@ -0,0 +1,334 @@
+use super::LeastCost;
+use crate::{manhattan_distance, Pos};
+use hashbrown::HashMap;
+use num::Zero;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+/// Parent and cost of every node reached so far, the parent of a start node is `None`.
+type Parents<N, C> = HashMap<N, (Option<N>, C)>;
+
+/// Finds a cheapest path with A*, in the style of [`dijkstra_multi`](super::dijkstra_multi).
+/// The `heuristic` estimates the remaining cost to a node where `stop` holds. It must never
+/// overestimate it, otherwise the path found might not be the cheapest one.
+///
+/// Returns the path including `start` and the stop node, along with its cost.
+pub fn astar<N, C, FN, IN, FH, FS>(
+    start: &N,
+    successors: FN,
+    heuristic: FH,
+    stop: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    best_first([start.clone()], successors, heuristic, stop)
+}
+
+/// A* between two positions with the Manhattan distance as heuristic. This requires every
+/// step to cost at least the distance it covers, as with unit cost moves on a grid.
+pub fn astar_pos<FN, IN>(start: &Pos, goal: &Pos, successors: FN) -> Option<(Vec<Pos>, usize)>
+where
+    FN: FnMut(&Pos) -> IN,
+    IN: IntoIterator<Item = (Pos, usize)>,
+{
+    astar(
+        start,
+        successors,
+        |pos| manhattan_distance(pos, goal) as usize,
+        |pos| pos == goal,
+    )
+}
+
+/// Dijkstra from several start nodes at once, e.g. to find the start closest to a target.
+/// Returns the cheapest path from any of the starts to a node where `stop` holds.
+pub fn dijkstra_sources<N, C, FN, IN, FS>(
+    starts: impl IntoIterator<Item = N>,
+    successors: FN,
+    stop: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    best_first(starts, successors, |_| C::zero(), stop)
+}
+
+fn best_first<N, C, FN, IN, FH, FS>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut stop: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut to_see = BinaryHeap::new();
+    let mut parents: Parents<N, C> = HashMap::new();
+    for start in starts {
+        to_see.push(LeastCost {
+            cost: heuristic(&start),
+            node: (start.clone(), C::zero()),
+        });
+        parents.insert(start, (None, C::zero()));
+    }
+
+    while let Some(LeastCost {
+        node: (node, cost), ..
+    }) = to_see.pop()
+    {
+        // skip entries that were pushed before a cheaper path to the node was found.
+        if parents[&node].1 < cost {
+            continue;
+        }
+        if stop(&node) {
+            return Some((path_to(&parents, &node), cost));
+        }
+        for (successor, move_cost) in successors(&node) {
+            let new_cost = cost + move_cost;
+            if parents
+                .get(&successor)
+                .is_some_and(|(_, old_cost)| *old_cost <= new_cost)
+            {
+                continue;
+            }
+            parents.insert(successor.clone(), (Some(node.clone()), new_cost));
+            to_see.push(LeastCost {
+                cost: new_cost + heuristic(&successor),
+                node: (successor, new_cost),
+            });
+        }
+    }
+
+    None
+}
+
+/// Finds a cheapest path by searching from `start` and `goal` at the same time until the
+/// searches meet, which visits far fewer nodes than Dijkstra on large graphs. It needs the
+/// `predecessors` of a node (with the cost of the edge to it), which are the same as the
+/// successors in undirected graphs.
+pub fn dijkstra_bidirectional<N, C, FN, IN, FP, IP>(
+    start: &N,
+    goal: &N,
+    mut successors: FN,
+    mut predecessors: FP,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FP: FnMut(&N) -> IP,
+    IP: IntoIterator<Item = (N, C)>,
+{
+    let mut forward = Frontier::new(start);
+    let mut backward = Frontier::new(goal);
+    let mut best = (start == goal).then(|| (start.clone(), C::zero()));
+
+    while let (Some(forward_cost), Some(backward_cost)) = (forward.peek(), backward.peek()) {
+        // no path through unsettled nodes can beat the best one anymore.
+        if best
+            .as_ref()
+            .is_some_and(|(_, cost)| forward_cost + backward_cost >= *cost)
+        {
+            break;
+        }
+
+        let reached = if forward_cost <= backward_cost {
+            forward.expand(&mut successors)
+        } else {
+            backward.expand(&mut predecessors)
+        };
+        for (node, _) in reached {
+            if let (Some(a), Some(b)) = (forward.cost(&node), backward.cost(&node)) {
+                if best.as_ref().map_or(true, |(_, cost)| a + b < *cost) {
+                    best = Some((node, a + b));
+                }
+            }
+        }
+    }
+
+    best.map(|(meeting, cost)| {
+        let mut path = path_to(&forward.parents, &meeting);
+        let mut rest = path_to(&backward.parents, &meeting);
+        rest.pop();
+        path.extend(rest.into_iter().rev());
+        (path, cost)
+    })
+}
+
+/// Finds a shortest path with unit cost edges by searching from `start` and `goal` at the
+/// same time, see [`dijkstra_bidirectional`]. The cost is the number of steps.
+pub fn bfs_bidirectional<N, FN, IN, FP, IP>(
+    start: &N,
+    goal: &N,
+    mut successors: FN,
+    mut predecessors: FP,
+) -> Option<(Vec<N>, usize)>
+where
+    N: Eq + Hash + Clone,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FP: FnMut(&N) -> IP,
+    IP: IntoIterator<Item = N>,
+{
+    dijkstra_bidirectional(
+        start,
+        goal,
+        |node| successors(node).into_iter().map(|n| (n, 1)),
+        |node| predecessors(node).into_iter().map(|n| (n, 1)),
+    )
+}
+
+/// One direction of a bidirectional search.
+struct Frontier<N, C> {
+    to_see: BinaryHeap<LeastCost<N, C>>,
+    parents: Parents<N, C>,
+}
+
+impl<N: Eq + Hash + Clone, C: Zero + Ord + Copy> Frontier<N, C> {
+    fn new(start: &N) -> Self {
+        let mut to_see = BinaryHeap::new();
+        to_see.push(LeastCost {
+            node: start.clone(),
+            cost: C::zero(),
+        });
+        let parents = HashMap::from_iter([(start.clone(), (None, C::zero()))]);
+        Self { to_see, parents }
+    }
+
+    /// The cost of the cheapest node left to expand.
+    fn peek(&mut self) -> Option<C> {
+        while let Some(LeastCost { node, cost }) = self.to_see.peek() {
+            if self.parents[node].1 < *cost {
+                self.to_see.pop();
+            } else {
+                return Some(*cost);
+            }
+        }
+        None
+    }
+
+    fn cost(&self, node: &N) -> Option<C> {
+        self.parents.get(node).map(|(_, cost)| *cost)
+    }
+
+    /// Expands the cheapest node, returning the nodes whose cost improved.
+    fn expand<FN, IN>(&mut self, successors: &mut FN) -> Vec<(N, C)>
+    where
+        FN: FnMut(&N) -> IN,
+        IN: IntoIterator<Item = (N, C)>,
+    {
+        let Some(LeastCost { node, cost }) = self.to_see.pop() else {
+            return vec![];
+        };
+        let mut reached = vec![];
+        for (successor, move_cost) in successors(&node) {
+            let new_cost = cost + move_cost;
+            if self.cost(&successor).is_some_and(|old| old <= new_cost) {
+                continue;
+            }
+            self.parents
+                .insert(successor.clone(), (Some(node.clone()), new_cost));
+            self.to_see.push(LeastCost {
+                node: successor.clone(),
+                cost: new_cost,
+            });
+            reached.push((successor, new_cost));
+        }
+        reached
+    }
+}
+
+/// Follows the parents back from `end`, returning the path from its start to `end`.
+fn path_to<N: Eq + Hash + Clone, C>(parents: &Parents<N, C>, end: &N) -> Vec<N> {
+    let mut path = vec![end.clone()];
+    while let Some((Some(parent), _)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Heading, DIRECTIONS};
+
+    // a 5x5 grid with a wall at x = 2 that has a gap at y = 4.
+    fn open_neighbors(pos: &Pos) -> Vec<Pos> {
+        DIRECTIONS
+            .iter()
+            .map(|dir| dir.forward_from(pos))
+            .filter(|p| (0..5).contains(&p.0) && (0..5).contains(&p.1))
+            .filter(|p| p.0 != 2 || p.1 == 4)
+            .collect()
+    }
+
+    fn weighted(pos: &Pos) -> Vec<(Pos, usize)> {
+        open_neighbors(pos).into_iter().map(|p| (p, 1)).collect()
+    }
+
+    #[test]
+    fn test_astar() {
+        let (path, cost) = astar_pos(&(0, 0), &(4, 0), weighted).unwrap();
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+        assert_eq!((path[0], path[12]), ((0, 0), (4, 0)));
+        assert!(path.contains(&(2, 4)));
+        assert_eq!(astar_pos(&(0, 0), &(9, 9), weighted), None);
+    }
+
+    #[test]
+    fn test_dijkstra_sources() {
+        let (path, cost) = dijkstra_sources([(0, 0), (3, 3)], weighted, |p| *p == (4, 0)).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(path.first(), Some(&(3, 3)));
+    }
+
+    #[test]
+    fn test_bidirectional() {
+        let (path, cost) =
+            bfs_bidirectional(&(0, 0), &(4, 0), open_neighbors, open_neighbors).unwrap();
+        assert_eq!(cost, 12);
+        assert_eq!(path.len(), 13);
+        assert!(path
+            .windows(2)
+            .all(|w| manhattan_distance(&w[0], &w[1]) == 1));
+        assert_eq!(
+            bfs_bidirectional(&(1, 1), &(1, 1), open_neighbors, open_neighbors),
+            Some((vec![(1, 1)], 0))
+        );
+
+        // a shortcut that is only cheaper in one direction.
+        let edges = |n: &u8| match n {
+            0 => vec![(1, 5), (2, 1)],
+            2 => vec![(3, 1)],
+            3 => vec![(1, 1)],
+            _ => vec![],
+        };
+        let reverse = |n: &u8| match n {
+            1 => vec![(0, 5), (3, 1)],
+            2 => vec![(0, 1)],
+            3 => vec![(2, 1)],
+            _ => vec![],
+        };
+        assert_eq!(
+            dijkstra_bidirectional(&0, &1, edges, reverse),
+            Some((vec![0, 2, 3, 1], 3))
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use crate::{pos_from, Direction, Direction8, Grid, Heading, Pos};
+use aoc_parse::{parser, prelude::char_of, prelude::lines, Parser};
+use hashbrown::HashSet;
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// A dense grid backed by a flat `Vec`, for puzzles that look up the cell at a position
+/// rather than the positions of a char (see [`Grid`]):
+///
+/// ```
+/// let map = parser!(dense_grid(digit)).parse(input).unwrap();
+/// let starts = map.find_all(&0);                  // => HashSet<Pos>
+/// for pos in map.neighbors4(&start) {
+///     if map[pos] == map[start] + 1 { ... }
+/// }
+/// ```
+///
+/// Indexing with a position outside the grid panics, use [`Grid2D::get`] to check.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Grid2D<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid2D<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(Pos) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| pos_from(x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from its rows. Panics if the rows differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "Grid rows must have the same length"
+        );
+        Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// As with [`Grid`], the largest position inside the grid.
+    pub fn bounds(&self) -> Pos {
+        (self.width as isize - 1, self.height as isize - 1)
+    }
+
+    pub fn contains(&self, pos: &Pos) -> bool {
+        self.offset(pos).is_some()
+    }
+
+    pub fn get(&self, pos: &Pos) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: &Pos) -> Option<&mut T> {
+        self.offset(pos).map(|i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterates over all positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| pos_from(x, y)))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (Pos, &mut T)> {
+        self.positions().zip(self.cells.iter_mut())
+    }
+
+    /// The neighbors of a position in the directions of `D` that are inside the grid.
+    pub fn neighbors<D: Heading>(&self, pos: &Pos) -> impl Iterator<Item = Pos> + '_ {
+        let pos = *pos;
+        D::all()
+            .iter()
+            .map(move |dir| dir.forward_from(&pos))
+            .filter(|pos| self.contains(pos))
+    }
+
+    /// The orthogonal neighbors of a position that are inside the grid.
+    pub fn neighbors4(&self, pos: &Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbors::<Direction>(pos)
+    }
+
+    /// The orthogonal and diagonal neighbors of a position that are inside the grid.
+    pub fn neighbors8(&self, pos: &Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbors::<Direction8>(pos)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid2D<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid2D {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// The first position holding `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Pos>
+    where
+        T: PartialEq,
+    {
+        self.cells()
+            .find(|(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn find_all(&self, value: &T) -> HashSet<Pos>
+    where
+        T: PartialEq,
+    {
+        self.cells()
+            .filter(|(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    fn offset(&self, pos: &Pos) -> Option<usize> {
+        let (x, y) = (usize::try_from(pos.0).ok()?, usize::try_from(pos.1).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl Grid2D<char> {
+    /// Creates a dense grid from a [`Grid`], filling positions without a char with `empty`.
+    /// Positions outside the grid's bounds are left out.
+    pub fn from_grid(grid: &Grid, empty: char) -> Self {
+        let (width, height) = ((grid.bounds.0 + 1) as usize, (grid.bounds.1 + 1) as usize);
+        let mut dense = Self::new(width, height, empty);
+        for (c, positions) in &grid.map {
+            for pos in positions {
+                if let Some(cell) = dense.get_mut(pos) {
+                    *cell = *c;
+                }
+            }
+        }
+        dense
+    }
+
+    /// Converts to a [`Grid`], leaving out the positions holding `empty`.
+    pub fn to_grid(&self, empty: char) -> Grid {
+        let mut grid = Grid {
+            bounds: self.bounds(),
+            ..Grid::default()
+        };
+        for (pos, c) in self.cells() {
+            if *c != empty {
+                grid.update(*c, pos);
+            }
+        }
+        grid
+    }
+}
+
+impl<T> Index<Pos> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        &self[&pos]
+    }
+}
+
+impl<T> Index<&Pos> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, pos: &Pos) -> &Self::Output {
+        match self.offset(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("Position {pos:?} is outside the grid"),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid2D<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        &mut self[&pos]
+    }
+}
+
+impl<T> IndexMut<&Pos> for Grid2D<T> {
+    fn index_mut(&mut self, pos: &Pos) -> &mut Self::Output {
+        match self.offset(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("Position {pos:?} is outside the grid"),
+        }
+    }
+}
+
+impl<T: Display> Display for Grid2D<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Provides a parser for a dense grid with one cell per match of `cell`, e.g.
+/// `dense_grid(digit)` for a grid of heights.
+pub fn dense_grid<P>(cell: P) -> impl Parser<Output = Grid2D<P::Output>>
+where
+    P: Parser,
+{
+    parser!(rows:lines(cell+) => Grid2D::from_rows(rows))
+}
+
+/// Provides a parser for a dense grid of the given chars.
+pub fn dense_grid_of(chars: &'static str) -> impl Parser<Output = Grid2D<char>> {
+    parser!(g:dense_grid(char_of(chars)) => {
+        let c: Vec<char> = chars.chars().collect();
+        g.map(|i| c[*i])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid_of;
+    use aoc_parse::prelude::digit;
+
+    #[test]
+    fn test_indexing() {
+        let mut grid = Grid2D::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!((grid.width(), grid.height(), grid.bounds()), (3, 2, (2, 1)));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get(&(3, 0)), None);
+        assert_eq!(grid.get(&(-1, 0)), None);
+        grid[&(0, 1)] = 7;
+        assert_eq!(grid.row(1), &[7, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid2D::new(3, 3, 0);
+        assert_eq!(grid.neighbors4(&(1, 1)).count(), 4);
+        assert_eq!(grid.neighbors4(&(0, 0)).count(), 2);
+        assert_eq!(grid.neighbors8(&(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(&(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn test_parse_and_find() {
+        let grid = parser!(dense_grid(digit)).parse("012\n210\n").unwrap();
+        assert_eq!(grid.find(&2), Some((2, 0)));
+        assert_eq!(grid.find_all(&0), HashSet::from([(0, 0), (2, 1)]));
+        assert_eq!(grid.map(|d| d * 2)[(1, 1)], 2);
+        assert_eq!(grid.to_string(), "012\n210\n");
+    }
+
+    #[test]
+    fn test_grid_conversions() {
+        let input = "#.S\n..#\n";
+        let dense = parser!(dense_grid_of(".#S")).parse(input).unwrap();
+        let sparse = parser!(grid_of(".#S")).parse(input).unwrap();
+        assert_eq!(Grid2D::from_grid(&sparse, '.'), dense);
+
+        let mut grid = dense.to_grid('.');
+        assert_eq!(grid.bounds, sparse.bounds);
+        assert_eq!(grid.take_one('S'), (2, 0));
+        assert_eq!(grid.take_all('#'), HashSet::from([(0, 0), (2, 1)]));
+    }
+
+    #[test]
+    fn test_from_grid_out_of_bounds() {
+        // update doesn't widen the bounds, so the grid can hold positions outside them.
+        let mut grid = parser!(grid_of(".#"))
+            .parse(
+                "#.
+.#
+",
+            )
+            .unwrap();
+        grid.update('#', (5, 0));
+        grid.update('#', (-1, 1));
+        assert_eq!(Grid2D::from_grid(&grid, '.').to_string(), "#.\n.#\n");
+        assert_eq!(grid.render('.'), "#.\n.#\n");
+    }
+}
@@ -6,21 +6,45 @@ use std::cmp::PartialOrd;
 use std::fmt::Debug;
 use std::fmt::Display;
 
+mod cycle;
 mod digits;
 mod direction;
+mod distance;
 mod graph;
 mod grid;
+mod grid2d;
 mod line_segment;
+mod logging;
 mod maps;
+mod memo;
+mod moves;
+mod point;
+mod progress;
+mod region;
+mod render;
+mod transform;
+mod visualize;
 
 #[allow(unused_imports)]
 pub use aoc_parse::{parser, prelude::*, ParseIter};
+pub use cycle::*;
 pub use digits::*;
 pub use direction::*;
+pub use distance::*;
 pub use graph::*;
 pub use grid::*;
+pub use grid2d::*;
 pub use line_segment::*;
+pub use logging::*;
 pub use maps::*;
+pub use memo::*;
+pub use moves::*;
+pub use point::*;
+pub use progress::*;
+pub use region::*;
+pub use render::*;
+pub use transform::*;
+pub use visualize::*;
 
 #[allow(dead_code)]
 pub fn name_to_digit(s: &str) -> Option<u32> {
@@ -140,20 +164,13 @@ pub fn manhattan_distance<T: Num + Copy + PartialOrd>(p1: &(T, T), p2: &(T, T))
     d1 + d2
 }
 
+/// Rotates rectangular data clockwise, see [`transform`] for the other symmetries.
 #[allow(dead_code)]
 pub fn rotate<T: Copy>(data: &[Vec<T>]) -> Vec<Vec<T>> {
-    let n = data.len();
-    let mut new_data: Vec<Vec<T>> = vec![vec![data[0][0]; n]; n];
-
-    for (i, row) in data.iter().enumerate().take(n) {
-        for (j, d) in row.iter().enumerate().take(n) {
-            new_data[j][n - i - 1] = *d;
-        }
-    }
-
-    new_data
+    transform(data, Transform::Rotate90)
 }
 
+/// Rotates square data clockwise in place. Use [`rotate`] for data of other shapes.
 #[allow(dead_code)]
 pub fn rotate_mut<T: Copy>(data: &mut [Vec<T>]) {
     let n = data.len();
@@ -0,0 +1,101 @@
+use crate::{Grid, Overlay, Pos};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Levels of the logging macros. `solve -v` enables [`debug!`], `solve -vv` also [`trace!`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug = 1,
+    Trace = 2,
+}
+
+impl LogLevel {
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+#[inline]
+pub fn log_enabled(level: LogLevel) -> bool {
+    VERBOSITY.load(Ordering::Relaxed) >= level as u8
+}
+
+/// Sets how many levels are logged, 0 disables logging.
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+/// Logs a message to stderr when running with `solve -v`. The arguments are only
+/// evaluated when the level is enabled, so dumping state costs nothing otherwise:
+///
+/// ```
+/// debug!("after move {i}:\n{}", dump_grid(&grid));
+/// ```
+///
+/// The runner only enables logging for the first run of a part, and never with `--time`.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::__log!($crate::LogLevel::Debug, $($arg)*)
+    };
+}
+
+/// Logs a message to stderr when running with `solve -vv`. See [`debug!`].
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::__log!($crate::LogLevel::Trace, $($arg)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log {
+    ($level:expr, $($arg:tt)*) => {{
+        let level = $level;
+        if $crate::log_enabled(level) {
+            eprintln!("[{}] {}", level.name(), format_args!($($arg)*));
+        }
+    }};
+}
+
+/// Renders a grid for logging, one line per row.
+pub fn dump_grid(grid: &Grid) -> String {
+    grid.to_string()
+}
+
+/// Renders a set of positions as '#' on a '.' background for logging. The rendered area
+/// spans from (0, 0) to the largest coordinates, positions with negative coordinates are left out.
+pub fn dump_positions<'a, I>(positions: I) -> String
+where
+    I: IntoIterator<Item = &'a Pos>,
+    I::IntoIter: Clone,
+{
+    let positions = positions.into_iter();
+    let bounds = positions.clone().fold((0, 0), |bounds, pos| {
+        (bounds.0.max(pos.0), bounds.1.max(pos.1))
+    });
+    Overlay::new(bounds, '.').layer(positions, '#').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hashbrown::HashSet;
+
+    #[test]
+    fn test_dump_positions() {
+        let positions: HashSet<Pos> = [(0, 0), (2, 1), (-1, 0)].into_iter().collect();
+        assert_eq!(dump_positions(&positions), "#..\n..#\n");
+    }
+
+    #[test]
+    fn test_levels() {
+        assert!(LogLevel::Debug < LogLevel::Trace);
+        assert!(!log_enabled(LogLevel::Debug));
+    }
+}
@@ -1,5 +1,8 @@
 extern crate advent_of_code;
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, completions, decrypt, download, encrypt, generate, inspect, read, scaffold, solve, time,
+    verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -8,135 +11,235 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use crate::advent_of_code::template::Day;
-    use std::process;
+    use crate::advent_of_code::template::commands::solve::Visualize;
+    use crate::advent_of_code::template::{Day, InputSource};
+    use clap::{Args, CommandFactory, Parser, Subcommand};
+    use clap_complete::Shell;
 
+    /// Scaffold, solve and benchmark Advent of Code solutions.
+    #[derive(Parser)]
+    #[command(name = "advent_of_code", version)]
+    struct Cli {
+        #[command(subcommand)]
+        command: AppArguments,
+    }
+
+    #[derive(Subcommand)]
     pub enum AppArguments {
+        /// Download the input and puzzle description of a day (requires aoc-cli).
         Download {
+            /// Day to download (1-25).
             day: Day,
         },
+        /// Read the puzzle description of a day in the terminal (requires aoc-cli).
         Read {
+            /// Day to read (1-25).
+            day: Day,
+        },
+        /// Describe the shape of a day's input to help pick a parser.
+        Inspect {
+            /// Day to inspect (1-25).
             day: Day,
+            /// Inspect the example instead of the puzzle input.
+            #[arg(long)]
+            example: bool,
         },
+        /// Create the solution, input and example files of a day.
         Scaffold {
+            /// Day to scaffold (1-25).
             day: Day,
+            /// Also download the input and puzzle description (requires aoc-cli).
+            #[arg(long)]
             download: bool,
         },
+        /// Run the solution of a day against its puzzle input or another input.
         Solve {
+            /// Day to solve (1-25).
             day: Day,
+            /// Run an optimized build.
+            #[arg(long)]
             release: bool,
+            /// Profile heap allocations with DHAT.
+            #[arg(long)]
             dhat: bool,
-            submit: Option<u8>,
+            /// Confirm and submit the results of all unsolved parts (requires aoc-cli).
+            #[arg(long, conflicts_with_all = ["generated", "input_set"])]
+            submit: bool,
+            #[command(flatten)]
+            input: InputArgs,
+            /// Hide cargo's build output.
+            #[arg(long)]
             quiet: bool,
+            /// Print the `debug!` logs of the solution, repeat (-vv) to also print `trace!` logs.
+            #[arg(short, long, action = clap::ArgAction::Count)]
+            verbose: u8,
+            #[command(flatten)]
+            visualize: VisualizeArgs,
         },
+        /// Run the solutions of all days.
         All {
+            /// Run an optimized build.
+            #[arg(long)]
             release: bool,
+            #[command(flatten)]
+            input: InputArgs,
         },
+        /// Benchmark solutions. Without a day, only days without stored timings are benched.
         Time {
+            /// Bench all days.
+            #[arg(long, conflicts_with = "day")]
             all: bool,
+            /// Day to bench (1-25).
             day: Option<Day>,
+            /// Store the timings in the readme.
+            #[arg(long, conflicts_with_all = ["generated", "input_set"])]
             store: bool,
+            #[command(flatten)]
+            input: InputArgs,
+        },
+        /// Check solutions against the recorded answers of the puzzle input and all input sets.
+        Verify {
+            /// Day to verify (1-25). Defaults to all days.
+            day: Option<Day>,
+            /// Record the results of input sets that have no recorded answers yet.
+            #[arg(long)]
+            record: bool,
+        },
+        /// Write a random input for a day to `data/generated` for stress-testing.
+        Generate {
+            /// Day to generate an input for (9, 14, 18, 22 or 23).
+            day: Day,
+            /// Number of entries (lines, robots, files, ...). Defaults to the size of a real input.
+            #[arg(long)]
+            size: Option<usize>,
+            /// Seed of the random generator. Defaults to a random seed, which is printed.
+            #[arg(long)]
+            seed: Option<u64>,
+        },
+        /// Encrypt all puzzle inputs, creating a key file if there is none yet.
+        Encrypt,
+        /// Decrypt all encrypted puzzle inputs with the key file.
+        Decrypt,
+        /// Print a shell completion script to stdout.
+        Completions {
+            /// Shell to generate completions for.
+            shell: Shell,
         },
+        /// Scaffold, download and read the current day.
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
-
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+    #[derive(Args)]
+    pub struct InputArgs {
+        /// Run against the inputs written by `generate` instead of the puzzle inputs.
+        #[arg(long)]
+        generated: bool,
+        /// Run against a named input set, e.g. `data/inputs/01/alice.txt`.
+        #[arg(long, value_name = "NAME", conflicts_with = "generated")]
+        input_set: Option<String>,
+    }
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-                quiet: args.contains("--quiet"),
-            },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+    impl InputArgs {
+        pub fn into_source(self) -> InputSource {
+            match self.input_set {
+                Some(name) => InputSource::Set(name),
+                None if self.generated => InputSource::Generated,
+                None => InputSource::Puzzle,
             }
-        };
+        }
+    }
+
+    #[derive(Args)]
+    pub struct VisualizeArgs {
+        /// Play the frames pushed by the solution as a terminal animation.
+        #[arg(long)]
+        visualize: bool,
+        /// Write the frames pushed by the solution to an animated GIF per part.
+        #[arg(long, value_name = "FILE")]
+        visualize_to: Option<String>,
+        /// Playback speed of the visualization.
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        fps: Option<u32>,
+    }
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+    impl VisualizeArgs {
+        pub fn into_visualize(self) -> Option<Visualize> {
+            (self.visualize || self.visualize_to.is_some()).then_some(Visualize {
+                output: self.visualize_to,
+                fps: self.fps,
+            })
         }
+    }
+
+    /// Parses the command line, printing help or usage errors and exiting if needed.
+    pub fn parse() -> AppArguments {
+        Cli::parse().command
+    }
 
-        Ok(app_args)
+    pub fn command() -> clap::Command {
+        Cli::command()
     }
 }
 
 fn main() {
     match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+        AppArguments::All { release, input } => all::handle(release, input.into_source()),
+        AppArguments::Time {
+            day,
+            all,
+            store,
+            input,
+        } => time::handle(day, all, store, input.into_source()),
+        AppArguments::Verify { day, record } => verify::handle(day, record),
+        AppArguments::Download { day } => download::handle(day),
+        AppArguments::Read { day } => read::handle(day),
+        AppArguments::Inspect { day, example } => inspect::handle(day, example),
+        AppArguments::Scaffold { day, download } => {
+            scaffold::handle(day);
+            if download {
+                download::handle(day);
+            }
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
-                if download {
+        AppArguments::Solve {
+            day,
+            release,
+            dhat,
+            submit,
+            input,
+            quiet,
+            verbose,
+            visualize,
+        } => solve::handle(
+            day,
+            release,
+            dhat,
+            submit,
+            input.into_source(),
+            quiet,
+            verbose,
+            visualize.into_visualize(),
+        ),
+        AppArguments::Generate { day, size, seed } => generate::handle(day, size, seed),
+        AppArguments::Encrypt => encrypt::handle(),
+        AppArguments::Decrypt => decrypt::handle(),
+        AppArguments::Completions { shell } => completions::handle(shell, args::command()),
+        #[cfg(feature = "today")]
+        AppArguments::Today => {
+            match Day::today() {
+                Some(day) => {
+                    scaffold::handle(day);
                     download::handle(day);
+                    read::handle(day)
                 }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-                quiet,
-            } => solve::handle(day, release, dhat, submit, quiet),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
-        },
+                None => {
+                    eprintln!(
+                        "`today` command can only be run between the 1st and \
+                        the 25th of december. Please use `scaffold` with a specific day."
+                    );
+                    process::exit(1)
+                }
+            };
+        }
     };
 }
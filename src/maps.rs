@@ -89,6 +89,11 @@ impl<T: Clone + Eq + Hash + PartialEq> IdMap<T> {
         }
     }
 
+    /// The id of a key that was seen before, without adding it.
+    pub fn get(&self, index: &T) -> Option<usize> {
+        self.0.get(index).copied()
+    }
+
     pub fn key(&self, id: &usize) -> Option<&T> {
         self.0.iget(id)
     }
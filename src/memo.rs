@@ -0,0 +1,136 @@
+use hashbrown::HashMap;
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Invalidates the contents of every [`Memo`]. The runner calls this before each run and
+/// bench sample, so cached values never leak from one solve into the next.
+pub fn reset_memos() {
+    GENERATION.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A memoization cache scoped to a single solve. It can be used as a `static` for
+/// recursive free functions, or as a field of the puzzle struct:
+///
+/// ```
+/// static CACHE: Memo<(usize, usize), usize> = Memo::new();
+///
+/// fn blink(n: usize, times: usize) -> usize {
+///     CACHE.get_or_insert_with(&(n, times), || { ... })
+/// }
+/// ```
+///
+/// Values are cloned out of the cache, so the lock is not held while `f` runs and
+/// `f` may recurse into the same cache.
+pub struct Memo<K, V> {
+    cache: Mutex<Option<(usize, HashMap<K, V>)>>,
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub const fn new() -> Self {
+        Self {
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Returns the cached value for `key`, computing and storing it with `f` if missing.
+    pub fn get_or_insert_with<Q, F>(&self, key: &Q, f: F) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+        F: FnOnce() -> V,
+    {
+        if let Some(value) = self.get(key) {
+            return value;
+        }
+
+        let value = f();
+        self.with_cache(|cache| cache.insert(key.to_owned(), value.clone()));
+        value
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.with_cache(|cache| cache.get(key).cloned())
+    }
+
+    pub fn len(&self) -> usize {
+        self.with_cache(|cache| cache.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.with_cache(HashMap::clear);
+    }
+
+    /// Runs `f` on the cache of the current generation, dropping stale entries first.
+    fn with_cache<T>(&self, f: impl FnOnce(&mut HashMap<K, V>) -> T) -> T {
+        let generation = GENERATION.load(Ordering::Relaxed);
+        let mut guard = self.cache.lock().unwrap();
+        let (cache_generation, cache) = guard.get_or_insert_with(|| (generation, HashMap::new()));
+        if *cache_generation != generation {
+            *cache_generation = generation;
+            cache.clear();
+        }
+        f(cache)
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // reset_memos affects every memo, so tests checking cached contents can't run while
+    // another test resets them.
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    fn fib(memo: &Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_insert_with(&n, || fib(memo, n - 1) + fib(memo, n - 2))
+    }
+
+    #[test]
+    fn test_recursive_memo() {
+        let _lock = LOCK.lock().unwrap();
+        let memo = Memo::new();
+        assert_eq!(fib(&memo, 80), 23416728348467685);
+        assert_eq!(memo.len(), 79);
+        assert_eq!(memo.get(&10), Some(55));
+    }
+
+    #[test]
+    fn test_borrowed_keys() {
+        let _lock = LOCK.lock().unwrap();
+        let memo: Memo<String, usize> = Memo::new();
+        assert_eq!(memo.get_or_insert_with("abc", || 3), 3);
+        assert_eq!(memo.get_or_insert_with("abc", || 4), 3);
+        memo.clear();
+        assert!(memo.is_empty());
+    }
+
+    #[test]
+    fn test_reset_memos() {
+        static MEMO: Memo<u8, u8> = Memo::new();
+        let _lock = LOCK.lock().unwrap();
+        MEMO.get_or_insert_with(&1, || 1);
+        reset_memos();
+        assert_eq!(MEMO.get(&1), None);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{Direction, Grid, Heading, Pos, DIRECTIONS};
+use hashbrown::HashSet;
+
+/// A position with the direction it is facing, e.g. a reindeer in a maze.
+pub type Facing = (Pos, Direction);
+
+/// Successor functions for walking around the walls of a grid, to plug into
+/// [`dijkstra_multi`](crate::dijkstra_multi), [`astar_pos`](crate::astar_pos) or the
+/// pathfinding crate:
+///
+/// ```
+/// let moves = GridMoves::new(&corrupted).bounded((70, 70));
+/// bfs(&start, |pos| moves.neighbors(pos), |pos| *pos == end);
+///
+/// let moves = GridMoves::new(&walls).costs(1, 1000);
+/// dijkstra(&(start, Direction::East), |s| moves.turns(s), |s| s.0 == end);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct GridMoves<'a> {
+    walls: Option<&'a HashSet<Pos>>,
+    bounds: Option<Pos>,
+    forward_cost: usize,
+    turn_cost: usize,
+}
+
+impl<'a> GridMoves<'a> {
+    /// Moves anywhere but into `walls`, each step costing 1 and turning nothing.
+    pub fn new(walls: &'a HashSet<Pos>) -> Self {
+        Self {
+            walls: Some(walls),
+            bounds: None,
+            forward_cost: 1,
+            turn_cost: 0,
+        }
+    }
+
+    /// Moves around the `wall` chars of a grid without leaving its bounds.
+    pub fn from_grid(grid: &'a Grid, wall: char) -> Self {
+        Self {
+            walls: grid.map.get(&wall),
+            bounds: Some(grid.bounds),
+            forward_cost: 1,
+            turn_cost: 0,
+        }
+    }
+
+    /// Keeps the moves between (0, 0) and `bounds` (inclusive).
+    pub fn bounded(self, bounds: Pos) -> Self {
+        Self {
+            bounds: Some(bounds),
+            ..self
+        }
+    }
+
+    /// Sets the cost of a step forward and of a quarter turn in place.
+    pub fn costs(self, forward_cost: usize, turn_cost: usize) -> Self {
+        Self {
+            forward_cost,
+            turn_cost,
+            ..self
+        }
+    }
+
+    /// Whether a position is inside the bounds and not a wall.
+    pub fn is_open(&self, pos: &Pos) -> bool {
+        let inside = self.bounds.map_or(true, |(bx, by)| {
+            (0..=bx).contains(&pos.0) && (0..=by).contains(&pos.1)
+        });
+        inside && !self.walls.is_some_and(|walls| walls.contains(pos))
+    }
+
+    /// The open positions next to `pos`, for unweighted searches.
+    pub fn neighbors(&self, pos: &Pos) -> impl Iterator<Item = Pos> + 'a {
+        let (moves, pos) = (*self, *pos);
+        DIRECTIONS
+            .iter()
+            .map(move |dir| dir.forward_from(&pos))
+            .filter(move |pos2| moves.is_open(pos2))
+    }
+
+    /// The open positions next to `pos` with the cost of a step.
+    pub fn successors(&self, pos: &Pos) -> impl Iterator<Item = (Pos, usize)> + 'a {
+        let cost = self.forward_cost;
+        self.neighbors(pos).map(move |pos2| (pos2, cost))
+    }
+
+    /// The states reachable from a position and facing: a step forward if it is open, or a
+    /// quarter turn either way on the spot.
+    pub fn turns(&self, state: &Facing) -> impl Iterator<Item = (Facing, usize)> + 'a {
+        let (pos, dir) = *state;
+        let ahead = dir.forward_from(&pos);
+        let forward = self
+            .is_open(&ahead)
+            .then_some(((ahead, dir), self.forward_cost));
+        forward.into_iter().chain([
+            ((pos, dir.turn_left()), self.turn_cost),
+            ((pos, dir.turn_right()), self.turn_cost),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dijkstra_multi_all, grid_of};
+    use aoc_parse::{parser, Parser};
+
+    #[test]
+    fn test_neighbors() {
+        let grid = parser!(grid_of(".#")).parse("..#\n.#.\n").unwrap();
+        let moves = GridMoves::from_grid(&grid, '#');
+        let mut open: Vec<Pos> = moves.neighbors(&(0, 0)).collect();
+        open.sort();
+        assert_eq!(open, vec![(0, 1), (1, 0)]);
+        assert_eq!(moves.neighbors(&(2, 1)).count(), 0);
+        assert_eq!(
+            moves.successors(&(1, 0)).collect::<Vec<_>>(),
+            vec![((0, 0), 1)]
+        );
+
+        let walls = HashSet::new();
+        assert_eq!(GridMoves::new(&walls).neighbors(&(0, 0)).count(), 4);
+        assert_eq!(
+            GridMoves::from_grid(&grid, 'x').neighbors(&(2, 1)).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_turns() {
+        let walls: HashSet<Pos> = [(1, 0)].into_iter().collect();
+        let moves = GridMoves::new(&walls).bounded((2, 1)).costs(1, 1000);
+        let blocked: Vec<_> = moves.turns(&((0, 0), Direction::East)).collect();
+        assert_eq!(blocked.len(), 2);
+        assert!(blocked.iter().all(|(_, cost)| *cost == 1000));
+
+        let paths = dijkstra_multi_all(
+            &((0, 0), Direction::East),
+            |state| moves.turns(state),
+            |state| state.0 == (2, 0),
+        )
+        .unwrap();
+        assert_eq!(paths.cost, 3004);
+    }
+}
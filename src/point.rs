@@ -0,0 +1,291 @@
+use crate::{Direction, Direction8, Heading, Pos};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position or offset on a grid with vector arithmetic. Converts from and to [`Pos`],
+/// so it can be used where the puzzle does math and `Pos` elsewhere:
+///
+/// ```
+/// let delta = a - b;
+/// let antinode = a + delta * 2;
+/// let wrapped = (p + v * 100).rem_euclid(&dims);
+/// let next = pos + Direction::North;
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    /// Wraps each coordinate into `0..size`, e.g. for positions on a torus.
+    pub fn rem_euclid(&self, size: &Point) -> Point {
+        Point::new(self.x.rem_euclid(size.x), self.y.rem_euclid(size.y))
+    }
+
+    pub fn dot(&self, other: &Point) -> isize {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// The number of orthogonal steps between two points.
+    pub fn manhattan(&self, other: &Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The number of orthogonal or diagonal steps between two points.
+    pub fn chebyshev(&self, other: &Point) -> usize {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The point with each coordinate replaced by its sign, e.g. the single step towards
+    /// another point on the same row, column or diagonal.
+    pub fn signum(&self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+}
+
+impl From<Pos> for Point {
+    fn from((x, y): Pos) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl From<Point> for Pos {
+    fn from(point: Point) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(dir: Direction) -> Self {
+        dir.delta().into()
+    }
+}
+
+impl From<Direction8> for Point {
+    fn from(dir: Direction8) -> Self {
+        dir.delta().into()
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl<T: Into<Point>> Add<T> for Point {
+    type Output = Point;
+
+    fn add(self, other: T) -> Self::Output {
+        let other = other.into();
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Into<Point>> Sub<T> for Point {
+    type Output = Point;
+
+    fn sub(self, other: T) -> Self::Output {
+        let other = other.into();
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Into<Point>> AddAssign<T> for Point {
+    fn add_assign(&mut self, other: T) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Into<Point>> SubAssign<T> for Point {
+    fn sub_assign(&mut self, other: T) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, n: isize) -> Self::Output {
+        Point::new(self.x * n, self.y * n)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// A position or offset in space, see [`Point`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3 {
+    pub x: isize,
+    pub y: isize,
+    pub z: isize,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3::new(0, 0, 0);
+
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Wraps each coordinate into `0..size`.
+    pub fn rem_euclid(&self, size: &Point3) -> Point3 {
+        Point3::new(
+            self.x.rem_euclid(size.x),
+            self.y.rem_euclid(size.y),
+            self.z.rem_euclid(size.z),
+        )
+    }
+
+    pub fn dot(&self, other: &Point3) -> isize {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(&self, other: &Point3) -> Point3 {
+        Point3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn manhattan(&self, other: &Point3) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn chebyshev(&self, other: &Point3) -> usize {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// The six points sharing a face with this one.
+    pub fn neighbors6(&self) -> impl Iterator<Item = Point3> {
+        let p = *self;
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |delta| p + Point3::from(delta))
+    }
+}
+
+impl From<(isize, isize, isize)> for Point3 {
+    fn from((x, y, z): (isize, isize, isize)) -> Self {
+        Point3::new(x, y, z)
+    }
+}
+
+impl From<Point3> for (isize, isize, isize) {
+    fn from(point: Point3) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Self::Output {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Self::Output {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, n: isize) -> Self::Output {
+        Point3::new(self.x * n, self.y * n, self.z * n)
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Self::Output {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(4, 3);
+        let b = Point::from((5, 5));
+        assert_eq!(a - b, Point::new(-1, -2));
+        assert_eq!(a + (a - b) * 2, Point::new(2, -1));
+        assert_eq!(-a, Point::new(-4, -3));
+        assert_eq!(a + Direction::North, Point::new(4, 2));
+        assert_eq!(a + Direction8::SouthWest, Point::new(3, 4));
+        assert_eq!(Pos::from(a + (1, 1)), (5, 4));
+        assert_eq!(a.dot(&b), 35);
+    }
+
+    #[test]
+    fn test_wrapping_and_distances() {
+        let p = Point::new(-3, 12).rem_euclid(&Point::new(11, 7));
+        assert_eq!(p, Point::new(8, 5));
+        assert_eq!(Point::ORIGIN.manhattan(&Point::new(-3, 4)), 7);
+        assert_eq!(Point::ORIGIN.chebyshev(&Point::new(-3, 4)), 4);
+        assert_eq!(Point::new(-3, 4).signum(), Point::new(-1, 1));
+    }
+
+    #[test]
+    fn test_point3() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::from((4, 5, 6));
+        assert_eq!(b - a, Point3::new(3, 3, 3));
+        assert_eq!(a.cross(&b), Point3::new(-3, 6, -3));
+        assert_eq!(a.dot(&b), 32);
+        assert_eq!(a.manhattan(&b), 9);
+        assert_eq!(a.chebyshev(&-b), 9);
+        assert_eq!(a.neighbors6().count(), 6);
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::{stderr, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Minimum time between two redraws of the status line.
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static DRAWN: AtomicBool = AtomicBool::new(false);
+static LAST_DRAW: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Reports that `done` of `total` steps of a long-running part are finished:
+///
+/// ```
+/// for n in 0..candidates {
+///     progress(n, candidates);
+///     ...
+/// }
+/// ```
+///
+/// The runner shows progress as a status line on stderr while the first run of a part is
+/// going on and stderr is a terminal. Otherwise, and while benching, this is a no-op.
+#[inline]
+pub fn progress(done: usize, total: usize) {
+    progress_message(|| {
+        let percent = 100.0 * done as f64 / total.max(1) as f64;
+        format!("{done}/{total} ({percent:.1}%)")
+    });
+}
+
+/// Reports progress with a custom message. The closure is only called when the status line
+/// is redrawn, at most every 100ms, so building the message costs nothing otherwise.
+#[inline]
+pub fn progress_message<F>(message: F)
+where
+    F: FnOnce() -> String,
+{
+    if is_reporting_progress() {
+        draw(message);
+    }
+}
+
+#[inline]
+pub fn is_reporting_progress() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Enables or disables progress reporting. The first status line is drawn after the
+/// redraw interval, so fast runs never show one.
+pub fn set_reporting_progress(enabled: bool) {
+    *LAST_DRAW.lock().unwrap() = Some(Instant::now());
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Disables progress reporting and clears the status line, if one was drawn.
+pub fn finish_progress() {
+    ENABLED.store(false, Ordering::Relaxed);
+    if DRAWN.swap(false, Ordering::Relaxed) {
+        let mut stderr = stderr();
+        let _ = write!(stderr, "\r\x1b[2K");
+        let _ = stderr.flush();
+    }
+}
+
+#[cold]
+fn draw<F>(message: F)
+where
+    F: FnOnce() -> String,
+{
+    // parallel solutions may report from several threads, one of them drawing is enough.
+    let Ok(mut last_draw) = LAST_DRAW.try_lock() else {
+        return;
+    };
+    if last_draw.is_some_and(|last| last.elapsed() < REDRAW_INTERVAL) {
+        return;
+    }
+    *last_draw = Some(Instant::now());
+
+    let mut stderr = stderr();
+    let _ = write!(stderr, "\r\x1b[2K{}", message());
+    let _ = stderr.flush();
+    DRAWN.store(true, Ordering::Relaxed);
+}
+
+/// Counts finished steps across threads, e.g. in a `rayon` iterator where steps don't
+/// finish in order:
+///
+/// ```
+/// let counter = ProgressCounter::new(candidates.len());
+/// candidates.par_iter().find_first(|c| {
+///     counter.tick();
+///     ...
+/// })
+/// ```
+pub struct ProgressCounter {
+    done: AtomicUsize,
+    total: usize,
+}
+
+impl ProgressCounter {
+    pub const fn new(total: usize) -> Self {
+        Self {
+            done: AtomicUsize::new(0),
+            total,
+        }
+    }
+
+    /// Marks one more step as finished and reports the new count.
+    #[inline]
+    pub fn tick(&self) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        progress(done, self.total);
+    }
+
+    pub fn done(&self) -> usize {
+        self.done.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_only_built_when_reporting() {
+        set_reporting_progress(false);
+        progress_message(|| unreachable!());
+
+        let counter = ProgressCounter::new(3);
+        counter.tick();
+        counter.tick();
+        assert_eq!(counter.done(), 2);
+    }
+}
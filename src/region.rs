@@ -0,0 +1,194 @@
+use crate::{flood_fill, Direction, Grid, Heading, Pos, DIRECTIONS};
+use hashbrown::HashSet;
+
+/// A connected set of cells, e.g. a garden plot or an enclosed area:
+///
+/// ```
+/// for (c, region) in grid.regions() {
+///     price += region.area() * region.sides();
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Region {
+    pub cells: HashSet<Pos>,
+    /// The cells at the edge of the region, once for every side facing out of the region.
+    pub border: Vec<(Pos, Direction)>,
+}
+
+impl Region {
+    pub fn new(cells: HashSet<Pos>) -> Self {
+        let border = cells
+            .iter()
+            .flat_map(|pos| DIRECTIONS.iter().map(move |dir| (*pos, *dir)))
+            .filter(|(pos, dir)| !cells.contains(&dir.forward_from(pos)))
+            .collect();
+        Self { cells, border }
+    }
+
+    pub fn contains(&self, pos: &Pos) -> bool {
+        self.cells.contains(pos)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn perimeter(&self) -> usize {
+        self.border.len()
+    }
+
+    /// The number of straight fence sections around the region, including those of holes.
+    pub fn sides(&self) -> usize {
+        let border: HashSet<&(Pos, Direction)> = self.border.iter().collect();
+        // every side is counted once, at the cell where it ends.
+        self.border
+            .iter()
+            .filter(|(pos, dir)| !border.contains(&(dir.turn_right().forward_from(pos), *dir)))
+            .count()
+    }
+
+    /// The number of corners of the region's outline, convex and concave.
+    pub fn corners(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|pos| {
+                DIRECTIONS
+                    .iter()
+                    .filter(|dir| {
+                        let side = self.contains(&dir.forward_from(pos));
+                        let next = dir.turn_right();
+                        let next_side = self.contains(&next.forward_from(pos));
+                        let diagonal = self.contains(&next.forward_from(&dir.forward_from(pos)));
+                        (!side && !next_side) || (side && next_side && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The smallest and largest coordinates of the region's cells.
+    pub fn bounding_box(&self) -> (Pos, Pos) {
+        self.cells.iter().fold(
+            ((isize::MAX, isize::MAX), (isize::MIN, isize::MIN)),
+            |(min, max), pos| {
+                (
+                    (min.0.min(pos.0), min.1.min(pos.1)),
+                    (max.0.max(pos.0), max.1.max(pos.1)),
+                )
+            },
+        )
+    }
+
+    /// The areas enclosed by the region, i.e. the connected areas of other cells that can't
+    /// reach the outside without crossing the region.
+    pub fn holes(&self) -> Vec<Region> {
+        if self.cells.is_empty() {
+            return vec![];
+        }
+        // leave a ring of outside cells around the region, which all end up in one component.
+        let (min, max) = self.bounding_box();
+        let (min, max) = ((min.0 - 1, min.1 - 1), (max.0 + 1, max.1 + 1));
+        components_where::<Direction, _>(&min, &max, |pos| !self.contains(pos))
+            .into_iter()
+            .filter(|area| !area.contains(&min))
+            .collect()
+    }
+}
+
+/// Splits a set of cells into its connected regions, with `D` as the connectivity:
+/// [`Direction`] for 4-connected and [`Direction8`](crate::Direction8) for 8-connected regions.
+pub fn components<D: Heading>(cells: &HashSet<Pos>) -> Vec<Region> {
+    let mut seen = HashSet::new();
+    let mut regions = vec![];
+    for start in cells {
+        if !seen.insert(*start) {
+            continue;
+        }
+        let mut region = HashSet::new();
+        flood_fill::<D, _, _>(
+            start,
+            |pos| {
+                region.insert(*pos);
+            },
+            |_, pos2, _| cells.contains(pos2) && seen.insert(*pos2),
+        );
+        regions.push(Region::new(region));
+    }
+    regions
+}
+
+/// The connected regions of the cells from `min` to `max` (inclusive) matching a predicate.
+pub fn components_where<D, F>(min: &Pos, max: &Pos, is_inside: F) -> Vec<Region>
+where
+    D: Heading,
+    F: Fn(&Pos) -> bool,
+{
+    let cells = (min.1..=max.1)
+        .flat_map(|y| (min.0..=max.0).map(move |x| (x, y)))
+        .filter(|pos| is_inside(pos))
+        .collect();
+    components::<D>(&cells)
+}
+
+impl Grid {
+    /// The 4-connected regions of each char.
+    pub fn regions(&self) -> Vec<(char, Region)> {
+        self.map
+            .iter()
+            .flat_map(|(c, positions)| {
+                components::<Direction>(positions)
+                    .into_iter()
+                    .map(|region| (*c, region))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid_of, Direction8};
+    use aoc_parse::{parser, Parser};
+
+    fn region_of(c: char, input: &str) -> Vec<Region> {
+        let grid = parser!(grid_of(".ABEX")).parse(input).unwrap();
+        grid.regions()
+            .into_iter()
+            .filter(|(c2, _)| *c2 == c)
+            .map(|(_, region)| region)
+            .collect()
+    }
+
+    #[test]
+    fn test_measures() {
+        let regions = region_of('A', "AAAA\nBBEA\nBBAA\nEEEA\n");
+        assert_eq!(regions.len(), 1);
+        let region = &regions[0];
+        assert_eq!(region.area(), 8);
+        assert_eq!(region.perimeter(), 18);
+        assert_eq!(region.sides(), 10);
+        assert_eq!(region.corners(), 10);
+        assert_eq!(region.bounding_box(), ((0, 0), (3, 3)));
+    }
+
+    #[test]
+    fn test_holes() {
+        let regions = region_of('X', "XXXXX\nXAXBX\nXXXXX\n");
+        let region = &regions[0];
+        assert_eq!(region.holes().len(), 2);
+        assert_eq!(region.sides(), 12);
+        assert_eq!(region.corners(), 12);
+        assert!(region_of('A', "XXXXX\nXAXBX\nXXXXX\n")[0]
+            .holes()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_connectivity() {
+        let cells: HashSet<Pos> = [(0, 0), (1, 1), (3, 3)].into_iter().collect();
+        assert_eq!(components::<Direction>(&cells).len(), 3);
+        assert_eq!(components::<Direction8>(&cells).len(), 2);
+        let lines = components_where::<Direction, _>(&(0, 0), &(4, 4), |pos| pos.1 % 2 == 0);
+        assert_eq!(lines.len(), 3);
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{Grid, Grid2D, Pos};
+use std::fmt::{Display, Formatter};
+
+const ANSI_REVERSE: &str = "\x1b[7m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+impl Grid {
+    /// Renders the grid as text, one line per row, with `empty` at positions without a char.
+    pub fn render(&self, empty: char) -> String {
+        Grid2D::from_grid(self, empty).to_string()
+    }
+
+    /// Compares two grids cell by cell, see [`GridDiff`].
+    pub fn diff(&self, other: &Grid, empty: char) -> GridDiff {
+        Grid2D::from_grid(self, empty).diff(&Grid2D::from_grid(other, empty))
+    }
+}
+
+/// Renders the grid with '.' as the empty char, use [`Grid::render`] for others.
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render('.'))
+    }
+}
+
+/// Draws sets of positions on top of each other, e.g. the path of day 6's guard over the
+/// obstacles:
+///
+/// ```
+/// let overlay = Overlay::new(bounds, '.')
+///     .layer(&obstacles, '#')
+///     .layer(&visited, 'X')
+///     .layer([&start], '^');
+/// debug!("\n{overlay}");
+/// ```
+///
+/// Later layers are drawn over earlier ones. Positions outside the bounds are left out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Overlay {
+    grid: Grid2D<char>,
+}
+
+impl Overlay {
+    /// Creates an overlay from (0, 0) to `bounds` filled with `empty`.
+    pub fn new(bounds: Pos, empty: char) -> Self {
+        let (width, height) = ((bounds.0 + 1).max(0), (bounds.1 + 1).max(0));
+        Self {
+            grid: Grid2D::new(width as usize, height as usize, empty),
+        }
+    }
+
+    /// Creates an overlay with the chars of a grid as the bottom layer.
+    pub fn from_grid(grid: &Grid, empty: char) -> Self {
+        Self {
+            grid: Grid2D::from_grid(grid, empty),
+        }
+    }
+
+    pub fn layer<'a, I>(mut self, positions: I, c: char) -> Self
+    where
+        I: IntoIterator<Item = &'a Pos>,
+    {
+        for pos in positions {
+            if let Some(cell) = self.grid.get_mut(pos) {
+                *cell = c;
+            }
+        }
+        self
+    }
+
+    pub fn into_grid(self) -> Grid2D<char> {
+        self.grid
+    }
+}
+
+impl Display for Overlay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+impl Grid2D<char> {
+    /// Compares two grids cell by cell, see [`GridDiff`].
+    pub fn diff(&self, other: &Grid2D<char>) -> GridDiff {
+        GridDiff::new(self, other)
+    }
+}
+
+/// The cells that differ between two grids. It is displayed as both grids side by side with
+/// the differing cells highlighted, followed by the number of differences:
+///
+/// ```
+/// debug!("expected / actual:\n{}", expected.diff(&warehouse));
+/// ```
+///
+/// Grids of different sizes are compared on the larger size, missing cells are shown as ' '.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GridDiff {
+    pub left: Grid2D<char>,
+    pub right: Grid2D<char>,
+    /// The positions that differ, row by row.
+    pub positions: Vec<Pos>,
+}
+
+impl GridDiff {
+    fn new(left: &Grid2D<char>, right: &Grid2D<char>) -> Self {
+        let width = left.width().max(right.width());
+        let height = left.height().max(right.height());
+        let pad = |grid: &Grid2D<char>| {
+            Grid2D::from_fn(width, height, |pos| grid.get(&pos).copied().unwrap_or(' '))
+        };
+        let (left, right) = (pad(left), pad(right));
+        let positions = left
+            .cells()
+            .filter(|(pos, c)| right[pos] != **c)
+            .map(|(pos, _)| pos)
+            .collect();
+
+        Self {
+            left,
+            right,
+            positions,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+impl Display for GridDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let write_row = |f: &mut Formatter<'_>, grid: &Grid2D<char>, y: usize| {
+            for (x, c) in grid.row(y).iter().enumerate() {
+                if self.left[(x as isize, y as isize)] != self.right[(x as isize, y as isize)] {
+                    write!(f, "{ANSI_REVERSE}{c}{ANSI_RESET}")?;
+                } else {
+                    write!(f, "{c}")?;
+                }
+            }
+            Ok(())
+        };
+
+        for y in 0..self.left.height() {
+            write_row(f, &self.left, y)?;
+            write!(f, "   ")?;
+            write_row(f, &self.right, y)?;
+            writeln!(f)?;
+        }
+        writeln!(f, "{} cell(s) differ", self.positions.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid_of;
+    use aoc_parse::{parser, Parser};
+    use hashbrown::HashSet;
+
+    #[test]
+    fn test_render_grid() {
+        let grid = parser!(grid_of(".#S")).parse("#..\n.S#\n").unwrap();
+        assert_eq!(grid.to_string(), "#..\n.S#\n");
+        assert_eq!(grid.render(' '), "#  \n S#\n");
+    }
+
+    #[test]
+    fn test_overlay() {
+        let walls: HashSet<Pos> = [(0, 0), (1, 0), (2, 0)].into_iter().collect();
+        let path = vec![(1, 0), (1, 1), (5, 5)];
+        let overlay = Overlay::new((2, 1), '.')
+            .layer(&walls, '#')
+            .layer(&path, 'o');
+        assert_eq!(overlay.to_string(), "#o#\n.o.\n");
+    }
+
+    #[test]
+    fn test_diff() {
+        let left = parser!(grid_of(".#O")).parse("#O.\n#..\n").unwrap();
+        let right = parser!(grid_of(".#O")).parse("#.O\n#..\n").unwrap();
+        let diff = left.diff(&right, '.');
+        assert_eq!(diff.positions, vec![(1, 0), (2, 0)]);
+        assert!(left.diff(&left, '.').is_empty());
+        assert_eq!(
+            diff.to_string(),
+            "#\x1b[7mO\x1b[0m\x1b[7m.\x1b[0m   #\x1b[7m.\x1b[0m\x1b[7mO\x1b[0m\n#..   #..\n2 cell(s) differ\n"
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, InputSource};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Represents the submission history of a single part.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartAnswer {
+    pub correct: Option<String>,
+    pub incorrect: Vec<String>,
+}
+
+impl PartAnswer {
+    pub fn is_solved(&self) -> bool {
+        self.correct.is_some()
+    }
+
+    pub fn was_rejected(&self, answer: &str) -> bool {
+        self.incorrect.iter().any(|x| x == answer)
+    }
+}
+
+/// Represents the known answers of a named input set, e.g. a teammate's input.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputAnswer {
+    pub name: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl InputAnswer {
+    pub fn part(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            _ => self.part_2.as_ref(),
+        }
+    }
+}
+
+/// Represents the submission history of a single day.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: PartAnswer,
+    pub part_2: PartAnswer,
+    pub inputs: Vec<InputAnswer>,
+}
+
+impl Answer {
+    pub fn new(day: Day) -> Self {
+        Answer {
+            day,
+            part_1: PartAnswer::default(),
+            part_2: PartAnswer::default(),
+            inputs: vec![],
+        }
+    }
+
+    pub fn part(&self, part: u8) -> &PartAnswer {
+        match part {
+            1 => &self.part_1,
+            _ => &self.part_2,
+        }
+    }
+
+    pub fn part_mut(&mut self, part: u8) -> &mut PartAnswer {
+        match part {
+            1 => &mut self.part_1,
+            _ => &mut self.part_2,
+        }
+    }
+}
+
+/// Represents the submitted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        let s = fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from);
+
+        match s {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    pub fn get(&self, day: Day) -> Answer {
+        self.data
+            .iter()
+            .find(|a| a.day == day)
+            .cloned()
+            .unwrap_or_else(|| Answer::new(day))
+    }
+
+    /// Record the correct answer of a part.
+    pub fn record_correct(&mut self, day: Day, part: u8, answer: &str) {
+        self.entry(day).part_mut(part).correct = Some(answer.to_string());
+    }
+
+    /// The answer a part should produce for an input. For the puzzle input, this is the
+    /// accepted submission.
+    pub fn expected(&self, day: Day, source: &InputSource, part: u8) -> Option<String> {
+        let answer = self.get(day);
+        match source {
+            InputSource::Puzzle => answer.part(part).correct.clone(),
+            InputSource::Set(name) => answer
+                .inputs
+                .iter()
+                .find(|input| input.name == *name)
+                .and_then(|input| input.part(part).cloned()),
+            InputSource::Generated => None,
+        }
+    }
+
+    /// Record the answer of a part for a named input set.
+    pub fn record_expected(&mut self, day: Day, name: &str, part: u8, answer: &str) {
+        let entry = self.entry(day);
+        let i = match entry.inputs.iter().position(|input| input.name == name) {
+            Some(i) => i,
+            None => {
+                entry.inputs.push(InputAnswer {
+                    name: name.to_string(),
+                    ..InputAnswer::default()
+                });
+                entry.inputs.len() - 1
+            }
+        };
+        let input = &mut entry.inputs[i];
+        match part {
+            1 => input.part_1 = Some(answer.to_string()),
+            _ => input.part_2 = Some(answer.to_string()),
+        }
+    }
+
+    /// Record an answer that was rejected for a part.
+    pub fn record_incorrect(&mut self, day: Day, part: u8, answer: &str) {
+        let part = self.entry(day).part_mut(part);
+        if !part.was_rejected(answer) {
+            part.incorrect.push(answer.to_string());
+        }
+    }
+
+    fn entry(&mut self, day: Day) -> &mut Answer {
+        let i = match self.data.iter().position(|a| a.day == day) {
+            Some(i) => i,
+            None => {
+                self.data.push(Answer::new(day));
+                self.data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+        &mut self.data[i]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part_1".into(), JsonValue::from(&value.part_1));
+        map.insert("part_2".into(), JsonValue::from(&value.part_2));
+
+        if !value.inputs.is_empty() {
+            map.insert(
+                "inputs".into(),
+                JsonValue::Array(value.inputs.iter().map(JsonValue::from).collect()),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .ok_or("Expected answer.part_1 to be an object.")
+            .and_then(PartAnswer::try_from)?;
+
+        let part_2 = json
+            .get("part_2")
+            .ok_or("Expected answer.part_2 to be an object.")
+            .and_then(PartAnswer::try_from)?;
+
+        let inputs = match json.get("inputs") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected answer.inputs to be an array.")?
+                .iter()
+                .map(InputAnswer::try_from)
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Answer {
+            day,
+            part_1,
+            part_2,
+            inputs,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartAnswer> for JsonValue {
+    fn from(value: &PartAnswer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "correct".into(),
+            match &value.correct {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "incorrect".into(),
+            JsonValue::Array(
+                value
+                    .incorrect
+                    .iter()
+                    .map(|x| JsonValue::String(x.clone()))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartAnswer {
+    type Error = &'static str;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part answer to be a JSON object.")?;
+
+        let correct = json
+            .get("correct")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected part.correct to be null or string.")?;
+
+        let incorrect = json
+            .get("incorrect")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .and_then(|v| v.iter().map(|x| x.get::<String>().cloned()).collect())
+            .ok_or("Expected part.incorrect to be an array of strings.")?;
+
+        Ok(PartAnswer {
+            correct: correct.cloned(),
+            incorrect,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&InputAnswer> for JsonValue {
+    fn from(value: &InputAnswer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        let part = |x: &Option<String>| match x {
+            Some(x) => JsonValue::String(x.clone()),
+            None => JsonValue::Null,
+        };
+
+        map.insert("name".into(), JsonValue::String(value.name.clone()));
+        map.insert("part_1".into(), part(&value.part_1));
+        map.insert("part_2".into(), part(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for InputAnswer {
+    type Error = &'static str;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected input answer to be a JSON object.")?;
+
+        let name = json
+            .get("name")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected input.name to be a string.")?;
+
+        let part = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or("Expected input parts to be null or string.")
+        };
+
+        Ok(InputAnswer {
+            name: name.clone(),
+            part_1: part("part_1")?.cloned(),
+            part_2: part("part_2")?.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use crate::{day, template::InputSource};
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": { "correct": "11", "incorrect": ["12"] }, "part_2": { "correct": null, "incorrect": [] } }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        let answer = answers.get(day!(1));
+        assert_eq!(answer.part_1.correct, Some("11".to_string()));
+        assert_eq!(answer.part_1.was_rejected("12"), true);
+        assert_eq!(answer.part_2.is_solved(), false);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_json() {
+        let json = r#"{}"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn records_outcomes() {
+        let mut answers = Answers::default();
+        answers.record_incorrect(day!(3), 1, "41");
+        answers.record_incorrect(day!(3), 1, "41");
+        answers.record_correct(day!(3), 1, "42");
+        answers.record_correct(day!(1), 2, "7");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(
+            answers.get(day!(3)).part_1.incorrect,
+            vec!["41".to_string()]
+        );
+        assert_eq!(answers.get(day!(3)).part_1.correct, Some("42".to_string()));
+    }
+
+    #[test]
+    fn looks_up_expected_answers() {
+        let mut answers = Answers::default();
+        answers.record_correct(day!(3), 1, "42");
+        answers.record_expected(day!(3), "alice", 2, "7");
+
+        let alice = InputSource::Set("alice".into());
+        assert_eq!(
+            answers.expected(day!(3), &InputSource::Puzzle, 1),
+            Some("42".into())
+        );
+        assert_eq!(answers.expected(day!(3), &alice, 1), None);
+        assert_eq!(answers.expected(day!(3), &alice, 2), Some("7".into()));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = Answers::default();
+        answers.record_incorrect(day!(3), 2, "41");
+        answers.record_correct(day!(3), 1, "42");
+        answers.record_expected(day!(3), "bob", 1, "43");
+
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(3)).part_1.correct, Some("42".to_string()));
+        assert_eq!(
+            answers.get(day!(3)).part_2.incorrect,
+            vec!["41".to_string()]
+        );
+        assert_eq!(
+            answers.get(day!(3)).inputs[0].part_1,
+            Some("43".to_string())
+        );
+    }
+}
@@ -72,12 +72,56 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Outcome of a submission, as reported by aoc-cli.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    /// The part has already been solved on the website.
+    AlreadySolved,
+    /// An answer was submitted too recently, nothing has been checked.
+    TooSoon,
+    Unknown,
+}
+
+impl SubmissionOutcome {
+    pub fn from_output(output: &str) -> Self {
+        if output.contains("That's the right answer") {
+            SubmissionOutcome::Correct
+        } else if output.contains("That's not the right answer") {
+            SubmissionOutcome::Incorrect
+        } else if output.contains("You don't seem to be solving the right level") {
+            SubmissionOutcome::AlreadySolved
+        } else if output.contains("You gave an answer too recently") {
+            SubmissionOutcome::TooSoon
+        } else {
+            SubmissionOutcome::Unknown
+        }
+    }
+}
+
+pub fn submit(day: Day, part: u8, result: &str) -> Result<SubmissionOutcome, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // aoc-cli's output is needed to find out whether the answer was accepted.
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    print!("{stdout}");
+
+    if output.status.success() {
+        Ok(SubmissionOutcome::from_output(&stdout))
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
 }
 
 fn get_input_path(day: Day) -> String {
@@ -123,3 +167,36 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SubmissionOutcome;
+
+    #[test]
+    fn parses_submission_outcomes() {
+        assert_eq!(
+            SubmissionOutcome::from_output(
+                "That's the right answer! You are one gold star closer."
+            ),
+            SubmissionOutcome::Correct
+        );
+        assert_eq!(
+            SubmissionOutcome::from_output("That's not the right answer; your answer is too low."),
+            SubmissionOutcome::Incorrect
+        );
+        assert_eq!(
+            SubmissionOutcome::from_output(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            SubmissionOutcome::AlreadySolved
+        );
+        assert_eq!(
+            SubmissionOutcome::from_output("You gave an answer too recently; you have to wait."),
+            SubmissionOutcome::TooSoon
+        );
+        assert_eq!(
+            SubmissionOutcome::from_output(""),
+            SubmissionOutcome::Unknown
+        );
+    }
+}
@@ -0,0 +1,9 @@
+use std::io;
+
+use clap::Command;
+use clap_complete::{generate, Shell};
+
+pub fn handle(shell: Shell, mut command: Command) {
+    let bin_name = command.get_name().to_string();
+    generate(shell, &mut command, bin_name, &mut io::stdout());
+}
@@ -0,0 +1,27 @@
+use std::{path::Path, process};
+
+use crate::template::encryption;
+
+pub fn handle() {
+    let key = match encryption::read_key() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Failed to read key file: {e}");
+            process::exit(1);
+        }
+    };
+
+    match encryption::decrypt_folder(&key, Path::new("data/inputs")) {
+        Ok(written) => {
+            for path in &written {
+                println!("Decrypted \"{}\"", path.display());
+            }
+            println!("---");
+            println!("🎄 Decrypted {} input file(s).", written.len());
+        }
+        Err(e) => {
+            eprintln!("Failed to decrypt inputs: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use std::{path::Path, process};
+
+use crate::template::encryption;
+
+pub fn handle() {
+    let (key, created) = match encryption::read_or_create_key() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("Failed to read key file: {e}");
+            process::exit(1);
+        }
+    };
+
+    if created {
+        println!(
+            "Created new key file \"{}\". Keep it out of version control and share it with your team (e.g. as a CI secret).",
+            encryption::key_path().display()
+        );
+    }
+
+    match encryption::encrypt_folder(&key, Path::new("data/inputs")) {
+        Ok(written) => {
+            for path in &written {
+                println!("Encrypted \"{}\"", path.display());
+            }
+            println!("---");
+            println!("🎄 Encrypted {} input file(s).", written.len());
+        }
+        Err(e) => {
+            eprintln!("Failed to encrypt inputs: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use std::{fs, path::Path, process, time::SystemTime};
+
+use crate::template::generators::{generator_for, Rng, GENERATED_FOLDER};
+use crate::template::Day;
+
+pub fn handle(day: Day, size: Option<usize>, seed: Option<u64>) {
+    let Some(generator) = generator_for(day) else {
+        eprintln!("There is no input generator for day {day}.");
+        process::exit(1);
+    };
+
+    // without a seed, pick one from the clock and print it so the input can be reproduced.
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let size = size.unwrap_or_else(|| generator.default_size());
+
+    let folder = Path::new("data").join(GENERATED_FOLDER);
+    let path = folder.join(format!("{day}.txt"));
+    let input = generator.generate(size, &mut Rng::new(seed));
+
+    if let Err(e) = fs::create_dir_all(&folder).and_then(|()| fs::write(&path, input)) {
+        eprintln!("Failed to write generated input: {e}");
+        process::exit(1);
+    }
+
+    println!(
+        "Generated input of size {size} with seed {seed} at \"{}\"",
+        path.display()
+    );
+    println!("---");
+    println!("🎄 Type `cargo solve {day} --generated` to run your solution against it.");
+}
@@ -0,0 +1,11 @@
+use crate::template::inspect::inspect;
+use crate::template::{read_file, Day};
+
+pub fn handle(day: Day, example: bool) {
+    let folder = if example { "examples" } else { "inputs" };
+    let input = read_file(folder, day);
+
+    println!("Inspecting \"data/{folder}/{day}.txt\"");
+    println!("---");
+    print!("{}", inspect(&input));
+}
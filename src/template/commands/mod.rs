@@ -1,6 +1,12 @@
 pub mod all;
+pub mod completions;
+pub mod decrypt;
 pub mod download;
+pub mod encrypt;
+pub mod generate;
+pub mod inspect;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+pub mod verify;
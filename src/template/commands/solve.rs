@@ -1,8 +1,23 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{Day, InputSource};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>, quiet: bool) {
+/// Options of `solve --visualize`, forwarded to the solution binary.
+pub struct Visualize {
+    pub output: Option<String>,
+    pub fps: Option<u32>,
+}
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit: bool,
+    input: InputSource,
+    quiet: bool,
+    verbose: u8,
+    visualize: Option<Visualize>,
+) {
     let mut cmd_args = vec!["run".to_string()];
 
     if quiet {
@@ -24,9 +39,24 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>, quie
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if submit {
         cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    for _ in 0..verbose {
+        cmd_args.push("-v".to_string());
+    }
+
+    if let Some(visualize) = visualize {
+        match visualize.output {
+            Some(output) => cmd_args.extend(["--visualize-to".to_string(), output]),
+            None => cmd_args.push("--visualize".to_string()),
+        }
+        if let Some(fps) = visualize.fps {
+            cmd_args.extend(["--fps".to_string(), fps.to_string()]);
+        }
     }
 
     let mut cmd = Command::new("cargo")
@@ -0,0 +1,76 @@
+use std::{path::Path, process, process::Command};
+
+use crate::template::inputs::input_sets;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{all_days, Day, InputSource, ANSI_BOLD, ANSI_RESET};
+
+/// Runs solutions against the puzzle input and every input set of a day, comparing
+/// their results with the recorded answers.
+pub fn handle(day: Option<Day>, record: bool) {
+    let days: Vec<Day> = day.map_or_else(
+        || {
+            all_days()
+                .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+                .collect()
+        },
+        |day| vec![day],
+    );
+
+    let mut failures = vec![];
+    let mut runs = 0;
+
+    for day in days {
+        let mut sources = vec![];
+        if InputSource::Puzzle.exists(day) {
+            sources.push(InputSource::Puzzle);
+        }
+        sources.extend(input_sets(day).into_iter().map(InputSource::Set));
+
+        for source in sources {
+            if runs > 0 {
+                println!();
+            }
+            runs += 1;
+
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET} ({source})");
+            println!("------");
+
+            let mut args = vec![
+                "run".to_string(),
+                "--quiet".to_string(),
+                "--release".to_string(),
+                "--bin".to_string(),
+                day.to_string(),
+                "--".to_string(),
+                "--verify".to_string(),
+            ];
+            args.extend(source.to_args());
+            if record {
+                args.push("--record".to_string());
+            }
+
+            let success = Command::new("cargo")
+                .args(&args)
+                .status()
+                .is_ok_and(|status| status.success());
+
+            if !success {
+                failures.push(format!("Day {day} ({source})"));
+            }
+        }
+    }
+
+    println!("---");
+    if failures.is_empty() {
+        println!("🎄 Verified {runs} input(s).");
+    } else {
+        println!(
+            "Verification failed for {} of {runs} input(s):",
+            failures.len()
+        );
+        for failure in &failures {
+            println!("  {failure}");
+        }
+        process::exit(1);
+    }
+}
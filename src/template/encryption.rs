@@ -0,0 +1,237 @@
+/// Transparent encryption of puzzle inputs at rest.
+///
+/// Advent of Code asks that puzzle inputs are not published. Instead of committing
+/// `data/inputs/{day}.txt`, an encrypted copy `data/inputs/{day}.txt.enc` can be committed.
+/// `read_file` falls back to decrypting that copy with the local key file whenever the
+/// plaintext is missing, so solutions, tests and benchmarks don't need to know about it.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+
+static KEY_FILE_PATH: &str = "./.aoc_input_key";
+static KEY_FILE_ENV: &str = "AOC_INPUT_KEY_FILE";
+
+pub static ENCRYPTED_EXTENSION: &str = "enc";
+
+const MAGIC: &[u8] = b"AOCENC1\n";
+const NONCE_SIZE: usize = 24;
+
+#[derive(Debug)]
+pub enum Error {
+    MissingKey(PathBuf),
+    InvalidKey(PathBuf),
+    InvalidFile(PathBuf),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingKey(path) => write!(
+                f,
+                "key file \"{}\" not found. Run `cargo encrypt` to create one or ask a teammate for it.",
+                path.display()
+            ),
+            Error::InvalidKey(path) => write!(
+                f,
+                "key file \"{}\" does not contain a 64 character hex key.",
+                path.display()
+            ),
+            Error::InvalidFile(path) => write!(
+                f,
+                "\"{}\" is not an encrypted input or was encrypted with a different key.",
+                path.display()
+            ),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Location of the key file. Defaults to `.aoc_input_key` in the repository root and can be
+/// overridden with the `AOC_INPUT_KEY_FILE` environment variable (e.g. in CI).
+#[must_use]
+pub fn key_path() -> PathBuf {
+    env::var(KEY_FILE_ENV).map_or_else(|_| PathBuf::from(KEY_FILE_PATH), PathBuf::from)
+}
+
+/// Returns the path of the encrypted copy of a plaintext file, e.g. `01.txt` => `01.txt.enc`.
+#[must_use]
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(".");
+    s.push(ENCRYPTED_EXTENSION);
+    PathBuf::from(s)
+}
+
+/// Reads the key from the key file.
+pub fn read_key() -> Result<Key, Error> {
+    let path = key_path();
+    let hex_key = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(Error::MissingKey(path)),
+        Err(e) => return Err(Error::IO(e)),
+    };
+
+    match hex::decode(hex_key.trim()) {
+        Ok(bytes) if bytes.len() == 32 => Ok(*Key::from_slice(&bytes)),
+        _ => Err(Error::InvalidKey(path)),
+    }
+}
+
+/// Reads the key from the key file, creating a new random key first if there is none yet.
+/// The boolean is `true` if a new key was created.
+pub fn read_or_create_key() -> Result<(Key, bool), Error> {
+    match read_key() {
+        Ok(key) => Ok((key, false)),
+        Err(Error::MissingKey(path)) => {
+            let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+            fs::write(path, format!("{}\n", hex::encode(key)))?;
+            Ok((key, true))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[must_use]
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let cipher = XChaCha20Poly1305::new(key);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .expect("encryption should not fail for in-memory buffers");
+
+    let mut data = Vec::with_capacity(MAGIC.len() + NONCE_SIZE + ciphertext.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+    data
+}
+
+/// Decrypts data produced by [`encrypt`]. Returns `None` if the data is malformed,
+/// has been tampered with or was encrypted with another key.
+#[must_use]
+pub fn decrypt(key: &Key, data: &[u8]) -> Option<Vec<u8>> {
+    let data = data.strip_prefix(MAGIC)?;
+    if data.len() < NONCE_SIZE {
+        return None;
+    }
+    let (nonce, ciphertext) = data.split_at(NONCE_SIZE);
+    XChaCha20Poly1305::new(key)
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .ok()
+}
+
+/// Reads and decrypts an encrypted file to a string.
+pub fn read_encrypted(path: &Path) -> Result<String, Error> {
+    let key = read_key()?;
+    let data = fs::read(path)?;
+    decrypt(&key, &data)
+        .and_then(|plaintext| String::from_utf8(plaintext).ok())
+        .ok_or_else(|| Error::InvalidFile(path.to_path_buf()))
+}
+
+/// Encrypts every `*.txt` file in `folder` and its subfolders, writing `*.txt.enc` next to it.
+/// Returns the paths of the written files.
+pub fn encrypt_folder(key: &Key, folder: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut written = vec![];
+    for path in files_with_extension(folder, "txt")? {
+        let target = encrypted_path(&path);
+        let plaintext = fs::read(&path)?;
+
+        // Skip files that haven't changed to keep the diff of the encrypted files minimal.
+        let unchanged = fs::read(&target)
+            .ok()
+            .and_then(|data| decrypt(key, &data))
+            .is_some_and(|existing| existing == plaintext);
+
+        if !unchanged {
+            fs::write(&target, encrypt(key, &plaintext))?;
+            written.push(target);
+        }
+    }
+    Ok(written)
+}
+
+/// Decrypts every `*.txt.enc` file in `folder` and its subfolders, writing the plaintext `*.txt` next to it.
+/// Returns the paths of the written files.
+pub fn decrypt_folder(key: &Key, folder: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut written = vec![];
+    for path in files_with_extension(folder, ENCRYPTED_EXTENSION)? {
+        let plaintext = decrypt(key, &fs::read(&path)?).ok_or(Error::InvalidFile(path.clone()))?;
+        let target = path.with_extension("");
+        fs::write(&target, plaintext)?;
+        written.push(target);
+    }
+    Ok(written)
+}
+
+/// Lists the files with an extension in `folder` and its subfolders (e.g. `data/inputs/01/alice.txt`).
+fn files_with_extension(folder: &Path, extension: &str) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = vec![];
+    for path in fs::read_dir(folder)?.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.is_dir() {
+            paths.extend(files_with_extension(&path, extension)?);
+        } else if path.extension().is_some_and(|ext| ext == extension) {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decrypt, encrypt, encrypted_path};
+    use chacha20poly1305::aead::{KeyInit, OsRng};
+    use chacha20poly1305::XChaCha20Poly1305;
+    use std::path::Path;
+
+    #[test]
+    fn roundtrips_plaintext() {
+        let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+        let data = encrypt(&key, b"1   2\n3   4\n");
+        assert_eq!(decrypt(&key, &data), Some(b"1   2\n3   4\n".to_vec()));
+    }
+
+    #[test]
+    fn uses_fresh_nonces() {
+        let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+        assert_ne!(encrypt(&key, b"input"), encrypt(&key, b"input"));
+    }
+
+    #[test]
+    fn rejects_wrong_key() {
+        let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+        let other = XChaCha20Poly1305::generate_key(&mut OsRng);
+        let data = encrypt(&key, b"input");
+        assert_eq!(decrypt(&other, &data), None);
+    }
+
+    #[test]
+    fn rejects_plaintext() {
+        let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+        assert_eq!(decrypt(&key, b"1   2\n3   4\n"), None);
+    }
+
+    #[test]
+    fn appends_extension() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/01.txt")),
+            Path::new("data/inputs/01.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,236 @@
+/// Random puzzle input generators used by the `generate` command to stress-test solutions
+/// on bigger or adversarial inputs than the real puzzle input.
+///
+/// Generated inputs are written to `data/generated/{day}.txt` and can be run with
+/// `cargo solve <day> --generated` or benched with `cargo time <day> --generated`.
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use hashbrown::HashSet;
+
+use crate::template::Day;
+
+pub static GENERATED_FOLDER: &str = "generated";
+
+/// A small, seedable PRNG (SplitMix64). It is part of the template so that a seed
+/// always produces the same input, independent of any crate version.
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in the inclusive range.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = range.end().abs_diff(*range.start()).saturating_add(1);
+        range.start().wrapping_add_unsigned(self.next_u64() % span)
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range(0..=i as i64) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+pub trait InputGenerator {
+    /// Size used when `--size` is not passed, roughly matching the real puzzle input.
+    fn default_size(&self) -> usize;
+
+    /// Generates an input of `size` entries (lines, robots, files, ...).
+    fn generate(&self, size: usize, rng: &mut Rng) -> String;
+}
+
+/// Returns the input generator of a day, if there is one.
+#[must_use]
+pub fn generator_for(day: Day) -> Option<Box<dyn InputGenerator>> {
+    match day.into_inner() {
+        9 => Some(Box::new(DiskMap)),
+        14 => Some(Box::new(Robots)),
+        18 => Some(Box::new(FallingBytes)),
+        22 => Some(Box::new(Secrets)),
+        23 => Some(Box::new(Network)),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Day 9: a single line of alternating file and free space lengths, `size` files long.
+struct DiskMap;
+
+impl InputGenerator for DiskMap {
+    fn default_size(&self) -> usize {
+        10_000
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut s = String::with_capacity(size * 2);
+        for i in 0..size {
+            if i > 0 {
+                s.push_str(&rng.range(0..=9).to_string());
+            }
+            s.push_str(&rng.range(1..=9).to_string());
+        }
+        s.push('\n');
+        s
+    }
+}
+
+/// Day 14: `size` robots with positions and velocities on the 101x103 floor.
+struct Robots;
+
+impl InputGenerator for Robots {
+    fn default_size(&self) -> usize {
+        500
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut s = String::new();
+        for _ in 0..size {
+            let _ = writeln!(
+                s,
+                "p={},{} v={},{}",
+                rng.range(0..=100),
+                rng.range(0..=102),
+                rng.range(-100..=100),
+                rng.range(-102..=102)
+            );
+        }
+        s
+    }
+}
+
+/// Day 18: `size` distinct byte positions on the 71x71 memory space, never the start or exit.
+struct FallingBytes;
+
+impl InputGenerator for FallingBytes {
+    fn default_size(&self) -> usize {
+        3450
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut positions: Vec<(i64, i64)> = (0..=70)
+            .flat_map(|y| (0..=70).map(move |x| (x, y)))
+            .filter(|pos| *pos != (0, 0) && *pos != (70, 70))
+            .collect();
+        rng.shuffle(&mut positions);
+
+        let mut s = String::new();
+        for (x, y) in positions.into_iter().take(size) {
+            let _ = writeln!(s, "{x},{y}");
+        }
+        s
+    }
+}
+
+/// Day 22: `size` initial secret numbers.
+struct Secrets;
+
+impl InputGenerator for Secrets {
+    fn default_size(&self) -> usize {
+        2000
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut s = String::new();
+        for _ in 0..size {
+            let _ = writeln!(s, "{}", rng.range(1..=(1 << 24) - 1));
+        }
+        s
+    }
+}
+
+/// Day 23: `size` distinct connections between computers with two letter names.
+struct Network;
+
+impl InputGenerator for Network {
+    fn default_size(&self) -> usize {
+        3380
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut names: Vec<String> = ('a'..='z')
+            .flat_map(|a| ('a'..='z').map(move |b| format!("{a}{b}")))
+            .collect();
+        rng.shuffle(&mut names);
+
+        // keep the average degree around 13 like in the real input.
+        names.truncate((size * 2 / 13).clamp(2, names.len()));
+        let size = size.min(names.len() * (names.len() - 1) / 2);
+
+        let mut seen = HashSet::new();
+        let mut s = String::new();
+        while seen.len() < size {
+            let a = rng.range(0..=names.len() as i64 - 1) as usize;
+            let b = rng.range(0..=names.len() as i64 - 1) as usize;
+            if a != b && seen.insert((a.min(b), a.max(b))) {
+                let _ = writeln!(s, "{}-{}", names[a], names[b]);
+            }
+        }
+        s
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generator_for, Rng};
+    use crate::day;
+
+    #[test]
+    fn is_deterministic() {
+        let generator = generator_for(day!(14)).unwrap();
+        let a = generator.generate(10, &mut Rng::new(42));
+        let b = generator.generate(10, &mut Rng::new(42));
+        let c = generator.generate(10, &mut Rng::new(43));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.lines().count(), 10);
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let n = rng.range(-3..=3);
+            assert!((-3..=3).contains(&n));
+        }
+    }
+
+    #[test]
+    fn generates_disk_maps() {
+        let input = generator_for(day!(9))
+            .unwrap()
+            .generate(5, &mut Rng::new(7));
+        assert_eq!(input.trim_end().len(), 9);
+    }
+
+    #[test]
+    fn generates_distinct_connections() {
+        let input = generator_for(day!(23))
+            .unwrap()
+            .generate(100, &mut Rng::new(7));
+        let mut pairs: Vec<&str> = input.lines().collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+        assert_eq!(pairs.len(), 100);
+    }
+
+    #[test]
+    fn has_no_generator_for_other_days() {
+        assert_eq!(generator_for(day!(1)).is_none(), true);
+    }
+}
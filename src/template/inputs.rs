@@ -0,0 +1,148 @@
+/// Selects which input a solution runs against.
+///
+/// Besides the puzzle input `data/inputs/{day}.txt`, a day can have named input sets in
+/// `data/inputs/{day}/{name}.txt`, e.g. the inputs of teammates, and an input written by
+/// the `generate` command.
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::template::encryption::{encrypted_path, ENCRYPTED_EXTENSION};
+use crate::template::generators::GENERATED_FOLDER;
+use crate::template::Day;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    #[default]
+    Puzzle,
+    Set(String),
+    Generated,
+}
+
+impl InputSource {
+    /// Parse the `--input-set <name>` and `--generated` arguments passed to the solution binary.
+    #[must_use]
+    pub fn from_args(args: &[String]) -> Self {
+        let input_set = args
+            .iter()
+            .position(|x| x == "--input-set")
+            .and_then(|i| args.get(i + 1));
+
+        match input_set {
+            Some(name) => InputSource::Set(name.clone()),
+            None if args.iter().any(|x| x == "--generated") => InputSource::Generated,
+            None => InputSource::Puzzle,
+        }
+    }
+
+    /// Arguments selecting this input when invoking a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Set(name) => vec!["--input-set".into(), name.clone()],
+            InputSource::Generated => vec!["--generated".into()],
+        }
+    }
+
+    #[must_use]
+    pub fn path(&self, day: Day) -> PathBuf {
+        let data = Path::new("data");
+        match self {
+            InputSource::Puzzle => data.join("inputs").join(format!("{day}.txt")),
+            InputSource::Set(name) => data
+                .join("inputs")
+                .join(day.to_string())
+                .join(format!("{name}.txt")),
+            InputSource::Generated => data.join(GENERATED_FOLDER).join(format!("{day}.txt")),
+        }
+    }
+
+    /// Checks if the input exists, either as plaintext or encrypted.
+    #[must_use]
+    pub fn exists(&self, day: Day) -> bool {
+        let path = self.path(day);
+        path.exists() || encrypted_path(&path).exists()
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Puzzle => write!(f, "puzzle input"),
+            InputSource::Set(name) => write!(f, "input set \"{name}\""),
+            InputSource::Generated => write!(f, "generated input"),
+        }
+    }
+}
+
+/// Returns the sorted names of the input sets of a day.
+#[must_use]
+pub fn input_sets(day: Day) -> Vec<String> {
+    let folder = Path::new("data").join("inputs").join(day.to_string());
+    let Ok(entries) = fs::read_dir(folder) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let name = name
+                .strip_suffix(&format!(".{ENCRYPTED_EXTENSION}"))
+                .unwrap_or(&name);
+            name.strip_suffix(".txt").map(str::to_string)
+        })
+        .collect();
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::day;
+    use std::path::Path;
+
+    #[test]
+    fn parses_args() {
+        let args = |s: &str| s.split(' ').map(String::from).collect::<Vec<_>>();
+        assert_eq!(
+            InputSource::from_args(&args("01 --time --input-set alice")),
+            InputSource::Set("alice".into())
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --generated")),
+            InputSource::Generated
+        );
+        assert_eq!(
+            InputSource::from_args(&args("01 --time")),
+            InputSource::Puzzle
+        );
+    }
+
+    #[test]
+    fn roundtrips_args() {
+        let source = InputSource::Set("bob".into());
+        let mut args = vec!["01".to_string()];
+        args.extend(source.to_args());
+        assert_eq!(InputSource::from_args(&args), source);
+    }
+
+    #[test]
+    fn builds_paths() {
+        assert_eq!(
+            InputSource::Puzzle.path(day!(1)),
+            Path::new("data/inputs/01.txt")
+        );
+        assert_eq!(
+            InputSource::Set("alice".into()).path(day!(1)),
+            Path::new("data/inputs/01/alice.txt")
+        );
+    }
+}
@@ -0,0 +1,539 @@
+/// Describes the shape of a puzzle input to help pick a parser, as printed by `inspect`.
+///
+/// The input is split into blank-line separated sections, which are analyzed on their own:
+/// rectangular sections are reported as grids with a character histogram, all other
+/// sections as token patterns with the range of every integer column.
+use std::fmt::{Display, Formatter, Write};
+
+use hashbrown::HashMap;
+
+/// Number of token patterns printed per section.
+const MAX_PATTERNS: usize = 5;
+/// Longer runs of digits are treated as digit strings rather than numbers.
+const MAX_INT_DIGITS: usize = 16;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Int(i64),
+    /// A run of digits too long to be a number, e.g. the disk map of day 9.
+    Digits(usize),
+    Word(String),
+    Symbol(char),
+}
+
+fn tokenize(line: &str) -> Vec<Token> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let is_negative = c == '-'
+            && chars.get(i + 1).is_some_and(char::is_ascii_digit)
+            && !i
+                .checked_sub(1)
+                .is_some_and(|j| chars[j].is_ascii_alphanumeric());
+
+        if c.is_ascii_digit() || is_negative {
+            let start = i;
+            i += 1;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let s: String = chars[start..i].iter().collect();
+            tokens.push(match s.parse() {
+                Ok(n) if s.len() <= MAX_INT_DIGITS => Token::Int(n),
+                _ => Token::Digits(s.len()),
+            });
+        } else if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_alphabetic() {
+                i += 1;
+            }
+            tokens.push(Token::Word(chars[start..i].iter().collect()));
+        } else {
+            tokens.push(Token::Symbol(c));
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+/// Renders tokens with integers replaced by `{int}`, and words by `{word}` if `generic_words` is set.
+fn pattern(tokens: &[Token], generic_words: bool) -> String {
+    let mut s = String::new();
+    for token in tokens {
+        match token {
+            Token::Int(_) => s.push_str("{int}"),
+            Token::Digits(_) => s.push_str("{digits}"),
+            Token::Word(_) if generic_words => s.push_str("{word}"),
+            Token::Word(w) => s.push_str(w),
+            Token::Symbol(c) => s.push(*c),
+        }
+    }
+    s
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Column {
+    pub min: i64,
+    pub max: i64,
+    /// Number of lines that have this column.
+    pub count: usize,
+}
+
+#[derive(Debug)]
+pub enum Shape {
+    Grid {
+        width: usize,
+        height: usize,
+        /// Characters with their number of occurrences, most frequent first.
+        histogram: Vec<(char, usize)>,
+    },
+    Text {
+        /// Token patterns with the number of lines matching them, most frequent first.
+        patterns: Vec<(String, usize)>,
+        /// Range of the n-th integer of each line.
+        columns: Vec<Column>,
+        suggestion: Option<String>,
+    },
+}
+
+#[derive(Debug)]
+pub struct Section {
+    /// 1-based line number of the first line of the section.
+    pub start: usize,
+    pub lines: usize,
+    pub shape: Shape,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub lines: usize,
+    pub sections: Vec<Section>,
+}
+
+#[must_use]
+pub fn inspect(input: &str) -> Report {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut sections = vec![];
+    let mut start = 0;
+
+    for (i, line) in lines.iter().enumerate().chain([(lines.len(), &"")]) {
+        if line.is_empty() {
+            if i > start {
+                sections.push(Section {
+                    start: start + 1,
+                    lines: i - start,
+                    shape: analyze(&lines[start..i]),
+                });
+            }
+            start = i + 1;
+        }
+    }
+
+    Report {
+        lines: lines.len(),
+        sections,
+    }
+}
+
+fn analyze(lines: &[&str]) -> Shape {
+    let tokens: Vec<Vec<Token>> = lines.iter().map(|line| tokenize(line)).collect();
+
+    let width = lines[0].chars().count();
+    let is_rectangular = lines.iter().all(|line| line.chars().count() == width);
+    let has_whitespace = lines.iter().any(|line| line.contains(char::is_whitespace));
+    // lines like `5,4` or `kh-tc` can be rectangular too, but are lists of values rather than grids.
+    let is_value_list = tokens.iter().all(|line| {
+        let values: Vec<&Token> = line
+            .iter()
+            .filter(|t| !matches!(t, Token::Symbol(_)))
+            .collect();
+        values.len() > 1
+            && (values.iter().all(|t| matches!(t, Token::Int(_)))
+                || values
+                    .iter()
+                    .any(|t| matches!(t, Token::Word(w) if w.len() > 1)))
+    });
+
+    if lines.len() > 1 && width > 1 && is_rectangular && !has_whitespace && !is_value_list {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for c in lines.iter().flat_map(|line| line.chars()) {
+            *counts.entry(c).or_default() += 1;
+        }
+        let mut histogram: Vec<(char, usize)> = counts.into_iter().collect();
+        histogram.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        return Shape::Grid {
+            width,
+            height: lines.len(),
+            histogram,
+        };
+    }
+
+    let mut columns: Vec<Column> = vec![];
+    for line in &tokens {
+        let ints = line.iter().filter_map(|t| match t {
+            Token::Int(n) => Some(*n),
+            _ => None,
+        });
+        for (i, n) in ints.enumerate() {
+            match columns.get_mut(i) {
+                Some(column) => {
+                    column.min = column.min.min(n);
+                    column.max = column.max.max(n);
+                    column.count += 1;
+                }
+                None => columns.push(Column {
+                    min: n,
+                    max: n,
+                    count: 1,
+                }),
+            }
+        }
+    }
+
+    // fall back to generic words if the lines only differ in their words, e.g. `kh-tc`.
+    let mut patterns = count_patterns(&tokens, false);
+    if patterns.len() > MAX_PATTERNS {
+        patterns = count_patterns(&tokens, true);
+    }
+
+    let suggestion = suggest_text(&tokens, &patterns, &columns).map(|p| {
+        if lines.len() == 1 {
+            format!("line({p})")
+        } else {
+            format!("lines({p})")
+        }
+    });
+
+    Shape::Text {
+        patterns,
+        columns,
+        suggestion,
+    }
+}
+
+fn count_patterns(tokens: &[Vec<Token>], generic_words: bool) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for line in tokens {
+        *counts.entry(pattern(line, generic_words)).or_default() += 1;
+    }
+    let mut patterns: Vec<(String, usize)> = counts.into_iter().collect();
+    patterns.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    patterns
+}
+
+fn suggest_text(
+    tokens: &[Vec<Token>],
+    patterns: &[(String, usize)],
+    columns: &[Column],
+) -> Option<String> {
+    let int_type = |column: Option<&Column>| match column {
+        Some(column) if column.min < 0 => "isize",
+        _ => "usize",
+    };
+
+    if let Some(list) = suggest_list(tokens, columns) {
+        return Some(list);
+    }
+
+    if patterns.len() != 1 {
+        return None;
+    }
+
+    let generic_words = patterns[0].0.contains("{word}");
+    let mut parts: Vec<String> = vec![];
+    let mut literal = String::new();
+    let mut column = 0;
+
+    for token in &tokens[0] {
+        let part = match token {
+            Token::Int(_) => {
+                column += 1;
+                int_type(columns.get(column - 1)).to_string()
+            }
+            Token::Digits(_) => "digit+".to_string(),
+            Token::Word(_) if generic_words => "string(alpha+)".to_string(),
+            Token::Word(w) => {
+                literal.push_str(w);
+                continue;
+            }
+            Token::Symbol(c) => {
+                literal.push(*c);
+                continue;
+            }
+        };
+        if !literal.is_empty() {
+            parts.push(format!("{literal:?}"));
+            literal.clear();
+        }
+        parts.push(part);
+    }
+    if !literal.is_empty() {
+        parts.push(format!("{literal:?}"));
+    }
+
+    Some(parts.join(" "))
+}
+
+/// Suggests `repeat_sep` for lists of values of one kind with a common separator,
+/// e.g. `7 6 4 2 1` or `r, wr, b, g`, if their length varies or they are long.
+fn suggest_list(tokens: &[Vec<Token>], columns: &[Column]) -> Option<String> {
+    let mut separator: Option<String> = None;
+    let mut lengths = vec![];
+    let mut is_int = None;
+
+    for line in tokens {
+        let mut values = 0;
+        let mut current = String::new();
+        for (i, token) in line.iter().enumerate() {
+            match token {
+                Token::Symbol(c) if i > 0 => current.push(*c),
+                Token::Int(_) | Token::Word(_) => {
+                    let int = matches!(token, Token::Int(_));
+                    if *is_int.get_or_insert(int) != int {
+                        return None;
+                    }
+                    if values > 0 {
+                        if *separator.get_or_insert_with(|| current.clone()) != current {
+                            return None;
+                        }
+                        current.clear();
+                    } else if !current.is_empty() {
+                        return None;
+                    }
+                    values += 1;
+                }
+                _ => return None,
+            }
+        }
+        if !current.is_empty() {
+            return None;
+        }
+        lengths.push(values);
+    }
+
+    let separator = separator?;
+    let varies = lengths.iter().any(|n| *n != lengths[0]);
+    if !varies && lengths[0] <= 4 {
+        return None;
+    }
+
+    let value = if is_int? {
+        if columns.iter().any(|c| c.min < 0) {
+            "isize"
+        } else {
+            "usize"
+        }
+    } else {
+        "string(alpha+)"
+    };
+    Some(format!("repeat_sep({value}, {separator:?})"))
+}
+
+impl Shape {
+    /// Suggests an `aoc-parse` parser for the section, if its shape is regular enough.
+    #[must_use]
+    pub fn suggestion(&self) -> Option<String> {
+        match self {
+            Shape::Grid { histogram, .. } => {
+                let chars: Vec<char> = histogram.iter().map(|(c, _)| *c).collect();
+                if chars.iter().all(char::is_ascii_digit) {
+                    Some("std_grid(digit)".into())
+                } else if chars.iter().all(char::is_ascii_uppercase) {
+                    Some("std_grid(upper)".into())
+                } else {
+                    // `grid_of` ignores its first char, so put the empty space first.
+                    let empty = if chars.contains(&'.') { '.' } else { chars[0] };
+                    let others: String = chars.iter().filter(|c| **c != empty).collect();
+                    Some(format!("grid_of({:?})", format!("{empty}{others}")))
+                }
+            }
+            Shape::Text { suggestion, .. } => suggestion.clone(),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} line(s) in {} section(s)",
+            self.lines,
+            self.sections.len()
+        )?;
+
+        for (i, section) in self.sections.iter().enumerate() {
+            writeln!(f)?;
+            writeln!(
+                f,
+                "Section {}: lines {}-{} ({} line(s))",
+                i + 1,
+                section.start,
+                section.start + section.lines - 1,
+                section.lines
+            )?;
+
+            match &section.shape {
+                Shape::Grid {
+                    width,
+                    height,
+                    histogram,
+                } => {
+                    writeln!(f, "  Grid: {width}x{height}")?;
+                    let mut s = String::new();
+                    for (c, count) in histogram {
+                        let _ = write!(s, " {c:?} x {count}");
+                    }
+                    writeln!(f, "  Characters:{s}")?;
+                }
+                Shape::Text {
+                    patterns, columns, ..
+                } => {
+                    writeln!(f, "  Patterns ({} distinct):", patterns.len())?;
+                    for (pattern, count) in patterns.iter().take(MAX_PATTERNS) {
+                        writeln!(f, "    {count} x {pattern}")?;
+                    }
+                    if patterns.len() > MAX_PATTERNS {
+                        writeln!(f, "    ...")?;
+                    }
+                    if !columns.is_empty() {
+                        writeln!(f, "  Integer columns:")?;
+                        for (i, column) in columns.iter().enumerate() {
+                            writeln!(
+                                f,
+                                "    {}: {}..={} ({} line(s))",
+                                i + 1,
+                                column.min,
+                                column.max,
+                                column.count
+                            )?;
+                        }
+                    }
+                }
+            }
+
+            if let Some(suggestion) = section.shape.suggestion() {
+                writeln!(f, "  Suggested parser: {suggestion}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{inspect, Column, Shape};
+
+    #[test]
+    fn detects_grids() {
+        let report = inspect("#.#\n.S.\n#.#\n");
+        assert_eq!(report.sections.len(), 1);
+        let shape = &report.sections[0].shape;
+        match shape {
+            Shape::Grid {
+                width,
+                height,
+                histogram,
+            } => {
+                assert_eq!((*width, *height), (3, 3));
+                assert_eq!(histogram[0], ('#', 4));
+            }
+            Shape::Text { .. } => panic!("expected a grid"),
+        }
+        assert_eq!(shape.suggestion(), Some("grid_of(\".#S\")".to_string()));
+    }
+
+    #[test]
+    fn detects_digit_grids() {
+        let report = inspect("0123\n1234\n");
+        assert_eq!(
+            report.sections[0].shape.suggestion(),
+            Some("std_grid(digit)".to_string())
+        );
+    }
+
+    #[test]
+    fn splits_sections() {
+        let report = inspect("##\n#.\n\n<^>\n");
+        assert_eq!(report.lines, 4);
+        assert_eq!(report.sections.len(), 2);
+        assert_eq!(report.sections[1].start, 4);
+        assert_eq!(
+            report.sections[1].shape.suggestion(),
+            Some("line(\"<^>\")".to_string())
+        );
+    }
+
+    #[test]
+    fn reports_integer_columns() {
+        let report = inspect("p=0,4 v=3,-3\np=6,3 v=-1,-3\n");
+        let shape = &report.sections[0].shape;
+        let Shape::Text { columns, .. } = shape else {
+            panic!("expected text");
+        };
+        assert_eq!(
+            columns[2],
+            Column {
+                min: -1,
+                max: 3,
+                count: 2
+            }
+        );
+        assert_eq!(
+            shape.suggestion(),
+            Some("lines(\"p=\" usize \",\" usize \" v=\" isize \",\" isize)".to_string())
+        );
+    }
+
+    #[test]
+    fn suggests_repeated_lists() {
+        let report = inspect("7 6 4 2 1\n1 2 7\n9 7 6 2\n");
+        assert_eq!(
+            report.sections[0].shape.suggestion(),
+            Some("lines(repeat_sep(usize, \" \"))".to_string())
+        );
+    }
+
+    #[test]
+    fn suggests_word_lists() {
+        let report = inspect("r, wr, b, g, bwu, rb, gb, br\n");
+        assert_eq!(
+            report.sections[0].shape.suggestion(),
+            Some("line(repeat_sep(string(alpha+), \", \"))".to_string())
+        );
+    }
+
+    #[test]
+    fn treats_number_pairs_as_text() {
+        let report = inspect("5,4\n4,2\n");
+        assert_eq!(
+            report.sections[0].shape.suggestion(),
+            Some("lines(usize \",\" usize)".to_string())
+        );
+    }
+
+    #[test]
+    fn falls_back_to_generic_words() {
+        let report = inspect("kh-tc\nqp-kh\nde-cg\nka-co\nyn-aq\nqp-ub\n");
+        assert_eq!(
+            report.sections[0].shape.suggestion(),
+            Some("lines(string(alpha+) \"-\" string(alpha+))".to_string())
+        );
+    }
+
+    #[test]
+    fn handles_long_digit_lines() {
+        let report = inspect("2333133121414131402\n");
+        assert_eq!(
+            report.sections[0].shape.suggestion(),
+            Some("line(digit+)".to_string())
+        );
+    }
+}
@@ -1,28 +1,44 @@
 #![allow(clippy::all)]
-use std::{env, fs};
+use std::{env, fs, path::Path};
 
 pub mod aoc_cli;
 pub mod commands;
 pub mod runner;
 
 pub use day::*;
+pub use inputs::InputSource;
 
+mod answers;
 mod day;
+mod encryption;
+mod generators;
+mod inputs;
+mod inspect;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod visualization;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+///
+/// If the file does not exist but an encrypted copy (e.g. `01.txt.enc`) does, the copy is
+/// decrypted with the local key file instead.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    read_to_string(&filepath)
+}
+
+/// Helper function that reads the input of a day from the given source.
+#[must_use]
+pub fn read_input(day: Day, source: &InputSource) -> String {
+    let cwd = env::current_dir().unwrap();
+    read_to_string(&cwd.join(source.path(day)))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -33,6 +49,18 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
         .join("data")
         .join(folder)
         .join(format!("{day}-{part}.txt"));
+    read_to_string(&filepath)
+}
+
+fn read_to_string(filepath: &Path) -> String {
+    let encrypted = encryption::encrypted_path(filepath);
+    if !filepath.exists() && encrypted.exists() {
+        return match encryption::read_encrypted(&encrypted) {
+            Ok(s) => s,
+            Err(e) => panic!("could not decrypt input file: {e}"),
+        };
+    }
+
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -40,6 +68,16 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// A `parse = <function>` parameter can be passed to parse the input only once. Its output is
+/// passed by reference to both parts, and its run time is reported separately:
+///
+/// ```
+/// advent_of_code::solution!(16, parse = Maze::new);
+///
+/// pub fn part_one(maze: &Maze) -> Option<usize> { ... }
+/// pub fn part_two(maze: &Maze) -> Option<usize> { ... }
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -51,19 +89,57 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let options = RunOptions::from_args();
+            let input = $crate::template::read_input(DAY, &options.input);
+            let parsed = run_parse($parse, &input, &options);
+            let results = [$( ($part, run_part($func, &parsed, &options, $part)), )*];
+            if options.submit {
+                submit_results(DAY, &results);
+            }
+            if options.verify {
+                verify_results(DAY, &options, &results);
+            }
+        }
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@header $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let options = RunOptions::from_args();
+            let input = $crate::template::read_input(DAY, &options.input);
+            let results = [$( ($part, run_part($func, &input, &options, $part)), )*];
+            if options.submit {
+                submit_results(DAY, &results);
+            }
+            if options.verify {
+                verify_results(DAY, &options, &results);
+            }
+        }
+    };
+
+    (@header $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
-
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
     };
 }
@@ -1,25 +1,106 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stderr, stdin, stdout, IsTerminal, Write};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::answers::Answers;
+use crate::template::aoc_cli::SubmissionOutcome;
+use crate::template::visualization;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, Day, InputSource, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Options passed to a solution binary, parsed once by the `solution!` macro.
+pub struct RunOptions {
+    pub time: bool,
+    pub submit: bool,
+    pub verify: bool,
+    pub record: bool,
+    pub input: InputSource,
+    pub visualize: Option<visualization::Options>,
+    /// Whether solutions can draw a progress status line, i.e. stderr is a terminal.
+    pub progress: bool,
+    /// Number of log levels enabled with `-v`, always 0 when timing.
+    pub verbosity: u8,
+}
+
+impl RunOptions {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let input = InputSource::from_args(&args);
+        let submit = args.iter().any(|x| x == "--submit");
+        let verify = args.iter().any(|x| x == "--verify");
+
+        if submit && input != InputSource::Puzzle {
+            eprintln!("Only results of the puzzle input can be submitted.");
+            process::exit(1);
+        }
+
+        if verify && input == InputSource::Generated {
+            eprintln!("Generated inputs have no answers to verify against.");
+            process::exit(1);
+        }
+
+        let time = args.iter().any(|x| x == "--time");
+        let verbosity = args
+            .iter()
+            .map(|x| match x.as_str() {
+                "-v" | "--verbose" => 1,
+                "-vv" => 2,
+                _ => 0,
+            })
+            .sum();
+
+        Self {
+            time,
+            submit,
+            verify,
+            record: args.iter().any(|x| x == "--record"),
+            input,
+            visualize: visualization::Options::from_args(&args),
+            progress: stderr().is_terminal(),
+            verbosity: if time { 0 } else { verbosity },
+        }
+    }
+}
+
+/// Runs and prints a solution part, returning its result for submission.
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    options: &RunOptions,
+    part: u8,
+) -> Option<String> {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    crate::set_visualizing(options.visualize.is_some());
+    crate::set_reporting_progress(options.progress);
+    crate::set_verbosity(options.verbosity);
+
+    let (result, duration, samples) = run_timed(func, input, options.time, |result| {
+        print_result(result, &part_str, "")
+    });
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(visualize) = &options.visualize {
+        visualization::present(&crate::take_frames(), visualize, part);
     }
+
+    result.map(|result| result.to_string())
+}
+
+/// Runs and times the shared `parse` function of a solution, returning its output for the parts.
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str, options: &RunOptions) -> P {
+    crate::set_reporting_progress(options.progress);
+    crate::set_verbosity(options.verbosity);
+    let (parsed, duration, samples) = run_timed(func, input, options.time, |_| print!("Parse:"));
+
+    print!("\r");
+    println!("Parse:{}", format_duration(&duration, samples));
+
+    parsed
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
@@ -28,8 +109,10 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
+    crate::reset_memos();
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -41,9 +124,14 @@ fn run_timed<I: Clone, T>(
     };
     let base_time = timer.elapsed();
 
+    // only the first run is visualized, reports progress and logs.
+    crate::set_visualizing(false);
+    crate::finish_progress();
+    crate::set_verbosity(0);
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if is_timed {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
@@ -71,6 +159,8 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
+        // start every sample with empty caches so timings don't measure cache hits.
+        crate::reset_memos();
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
         timers.push(timer.elapsed());
@@ -134,41 +224,120 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Submit the results of all unsolved parts if aoc-cli is installed.
+///
+/// Each answer is shown and has to be confirmed before it is submitted. Part two is only
+/// submitted once part one has been accepted. Outcomes are recorded in the answers store
+/// so solved parts and rejected answers are not submitted again.
+pub fn submit_results(day: Day, results: &[(u8, Option<String>)]) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let mut answers = Answers::read_from_file();
+
+    for (part, result) in results {
+        let previous = answers.get(day).part(*part).clone();
+
+        if let Some(correct) = &previous.correct {
+            println!("Part {part} is already solved ({ANSI_BOLD}{correct}{ANSI_RESET}).");
+            continue;
+        }
+
+        let Some(result) = result else {
+            println!("Part {part} has no result to submit.");
+            break;
+        };
+
+        if previous.was_rejected(result) {
+            println!("Part {part}: {ANSI_BOLD}{result}{ANSI_RESET} was already rejected, not submitting it again.");
+            break;
+        }
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+        if !confirm(&format!(
+            "Submit {ANSI_BOLD}{result}{ANSI_RESET} for part {part}? [y/N] "
+        )) {
+            break;
+        }
+
+        println!("Submitting result via aoc-cli...");
+        match aoc_cli::submit(day, *part, result) {
+            Ok(SubmissionOutcome::Correct) => {
+                answers.record_correct(day, *part, result);
+            }
+            Ok(SubmissionOutcome::Incorrect) => {
+                answers.record_incorrect(day, *part, result);
+                break;
+            }
+            Ok(SubmissionOutcome::AlreadySolved) => {
+                println!("Part {part} was already solved on the website.");
+            }
+            Ok(SubmissionOutcome::TooSoon | SubmissionOutcome::Unknown) => {
+                break;
+            }
+            Err(e) => {
+                eprintln!("failed to call aoc-cli: {e}");
+                break;
+            }
+        }
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if let Err(e) = answers.store_file() {
+        eprintln!("Failed to store answers: {e}");
     }
+}
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+/// Compares the results with the answers recorded for the input, exiting with an error on a mismatch.
+/// With `--record`, results of input sets without a recorded answer are stored as their answer.
+pub fn verify_results(day: Day, options: &RunOptions, results: &[(u8, Option<String>)]) {
+    let mut answers = Answers::read_from_file();
+    let mut failed = false;
+    let mut recorded = false;
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+    for (part, result) in results {
+        let expected = answers.expected(day, &options.input, *part);
+
+        match (expected, result, &options.input) {
+            (Some(expected), Some(result), _) if expected == *result => {
+                println!("Part {part}: ✓ matches the recorded answer.");
+            }
+            (Some(expected), result, _) => {
+                let result = result.as_deref().unwrap_or("no result");
+                println!("Part {part}: ✖ expected {ANSI_BOLD}{expected}{ANSI_RESET}, got {ANSI_BOLD}{result}{ANSI_RESET}.");
+                failed = true;
+            }
+            (None, Some(result), InputSource::Set(name)) if options.record => {
+                answers.record_expected(day, name, *part, result);
+                recorded = true;
+                println!("Part {part}: recorded {ANSI_BOLD}{result}{ANSI_RESET} as the answer.");
+            }
+            (None, _, InputSource::Puzzle) => {
+                println!(
+                    "Part {part}: no recorded answer, submit it with `--submit` to record it."
+                );
+            }
+            (None, _, _) => {
+                println!("Part {part}: no recorded answer, run with `--record` to record it.");
+            }
+        }
+    }
 
-    if part_submit != part {
-        return None;
+    if recorded {
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store answers: {e}");
+        }
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if failed {
         process::exit(1);
     }
+}
+
+fn confirm(prompt: &str) -> bool {
+    print!("{prompt}");
+    let _ = stdout().flush();
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let mut line = String::new();
+    stdin().read_line(&mut line).is_ok() && matches!(line.trim(), "y" | "Y" | "yes")
 }
@@ -0,0 +1,177 @@
+/// Presents the frames pushed by solutions via `advent_of_code::visualize` when running
+/// `solve --visualize`, either as a terminal animation or as an animated GIF.
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{self, stderr, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+use crate::{Color, Frame};
+
+const DEFAULT_FPS: u32 = 10;
+const PIXELS_PER_CELL: usize = 4;
+
+pub struct Options {
+    pub output: Option<PathBuf>,
+    pub fps: u32,
+}
+
+impl Options {
+    /// Parse the `--visualize`, `--visualize-to <file.gif>` and `--fps <n>` arguments
+    /// passed to the solution binary. Returns `None` if visualization was not requested.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        let output = value_of("--visualize-to").map(PathBuf::from);
+        if output.is_none() && !args.iter().any(|x| x == "--visualize") {
+            return None;
+        }
+
+        let fps = value_of("--fps")
+            .and_then(|x| x.parse().ok())
+            .filter(|fps| *fps > 0)
+            .unwrap_or(DEFAULT_FPS);
+
+        Some(Self { output, fps })
+    }
+}
+
+pub fn present(frames: &[Frame], options: &Options, part: u8) {
+    if frames.is_empty() {
+        eprintln!("Part {part}: no frames to visualize.");
+        return;
+    }
+
+    match &options.output {
+        Some(output) => {
+            let path = part_path(output, part);
+            match write_gif(frames, options.fps, &path) {
+                Ok(()) => eprintln!(
+                    "Part {part}: wrote {} frame(s) to \"{}\"",
+                    frames.len(),
+                    path.display()
+                ),
+                Err(e) => eprintln!("Part {part}: failed to write visualization: {e}"),
+            }
+        }
+        None => play(frames, options.fps),
+    }
+}
+
+/// Plays frames on stderr so that answer lines on stdout are never affected.
+fn play(frames: &[Frame], fps: u32) {
+    let delay = Duration::from_secs(1) / fps;
+    let mut stderr = stderr();
+
+    for frame in frames {
+        let mut s = String::from("\x1b[H\x1b[2J");
+        if !frame.label.is_empty() {
+            s.push_str(&format!("{ANSI_BOLD}{}{ANSI_RESET}\n", frame.label));
+        }
+        for row in frame.rows() {
+            for cell in row {
+                match cell.color {
+                    Some(Color(r, g, b)) => {
+                        s.push_str(&format!("\x1b[38;2;{r};{g};{b}m{}{ANSI_RESET}", cell.c));
+                    }
+                    None => s.push(cell.c),
+                }
+            }
+            s.push('\n');
+        }
+        let _ = stderr.write_all(s.as_bytes());
+        let _ = stderr.flush();
+        thread::sleep(delay);
+    }
+}
+
+fn write_gif(frames: &[Frame], fps: u32, path: &Path) -> Result<(), io::Error> {
+    let width = frames.iter().map(|f| f.width).max().unwrap_or(0) * PIXELS_PER_CELL;
+    let height = frames.iter().map(|f| f.height).max().unwrap_or(0) * PIXELS_PER_CELL;
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(io::Error::other("frames are too large for a GIF"));
+    };
+
+    let mut palette: Vec<Color> = vec![Color::BLACK];
+    let mut color_index = |color: Color| -> u8 {
+        let i = palette.iter().position(|c| *c == color).unwrap_or_else(|| {
+            palette.push(color);
+            palette.len() - 1
+        });
+        // GIFs support at most 256 colors, additional colors fall back to black.
+        u8::try_from(i).unwrap_or(0)
+    };
+
+    let images: Vec<Vec<u8>> = frames
+        .iter()
+        .map(|frame| {
+            let mut pixels = vec![0; width * height];
+            for (y, row) in frame.rows().enumerate() {
+                for (x, cell) in row.iter().enumerate() {
+                    let i = color_index(cell.color.unwrap_or_else(|| Color::default_for(cell.c)));
+                    for py in 0..PIXELS_PER_CELL {
+                        let start = (y * PIXELS_PER_CELL + py) * width + x * PIXELS_PER_CELL;
+                        pixels[start..start + PIXELS_PER_CELL].fill(i);
+                    }
+                }
+            }
+            pixels
+        })
+        .collect();
+
+    let mut rgb_palette: Vec<u8> = palette.iter().flat_map(|c| [c.0, c.1, c.2]).collect();
+    rgb_palette.truncate(256 * 3);
+
+    let delay = u16::try_from(100 / fps).unwrap_or(1).max(1);
+    let mut encoder = gif::Encoder::new(File::create(path)?, gif_width, gif_height, &rgb_palette)
+        .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+
+    for pixels in &images {
+        let frame = gif::Frame {
+            width: gif_width,
+            height: gif_height,
+            delay,
+            buffer: Cow::Borrowed(pixels),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+
+    Ok(())
+}
+
+/// Adds the part number to the output file, e.g. `15.gif` => `15-1.gif`.
+fn part_path(path: &Path, part: u8) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-{part}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{part}"),
+    };
+    path.with_file_name(name)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::part_path;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn adds_part_to_output_path() {
+        assert_eq!(
+            part_path(Path::new("out/15.gif"), 2),
+            PathBuf::from("out/15-2.gif")
+        );
+        assert_eq!(part_path(Path::new("15"), 1), PathBuf::from("15-1"));
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{Grid, Grid2D, Pos};
+
+/// One of the 8 symmetries of a rectangle. Rotations are clockwise as seen on screen,
+/// i.e. with y pointing down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Transform {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirrors left and right.
+    FlipHorizontal,
+    /// Mirrors top and bottom.
+    FlipVertical,
+    /// Mirrors along the diagonal from the top left corner.
+    Transpose,
+    /// Mirrors along the diagonal from the top right corner.
+    AntiTranspose,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::Transpose,
+        Transform::AntiTranspose,
+    ];
+
+    pub fn inverse(&self) -> Transform {
+        match self {
+            Transform::Rotate90 => Transform::Rotate270,
+            Transform::Rotate270 => Transform::Rotate90,
+            t => *t,
+        }
+    }
+
+    /// Whether width and height trade places.
+    pub fn swaps_axes(&self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::Transpose
+                | Transform::AntiTranspose
+        )
+    }
+
+    /// Maps the bounds (largest position) of a rectangle from (0, 0).
+    pub fn bounds(&self, bounds: &Pos) -> Pos {
+        if self.swaps_axes() {
+            (bounds.1, bounds.0)
+        } else {
+            *bounds
+        }
+    }
+
+    /// Maps a position inside the rectangle from (0, 0) to `bounds`.
+    pub fn apply(&self, pos: &Pos, bounds: &Pos) -> Pos {
+        let ((x, y), (bx, by)) = (*pos, *bounds);
+        match self {
+            Transform::Identity => (x, y),
+            Transform::Rotate90 => (by - y, x),
+            Transform::Rotate180 => (bx - x, by - y),
+            Transform::Rotate270 => (y, bx - x),
+            Transform::FlipHorizontal => (bx - x, y),
+            Transform::FlipVertical => (x, by - y),
+            Transform::Transpose => (y, x),
+            Transform::AntiTranspose => (by - y, bx - x),
+        }
+    }
+}
+
+impl<T: Clone> Grid2D<T> {
+    pub fn transform(&self, transform: Transform) -> Grid2D<T> {
+        let bounds = transform.bounds(&self.bounds());
+        let inverse = transform.inverse();
+        let (width, height) = ((bounds.0 + 1) as usize, (bounds.1 + 1) as usize);
+        Grid2D::from_fn(width, height, |pos| {
+            self[inverse.apply(&pos, &bounds)].clone()
+        })
+    }
+
+    /// All 8 symmetries of the grid, in the order of [`Transform::ALL`].
+    pub fn symmetries(&self) -> impl Iterator<Item = Grid2D<T>> + '_ {
+        Transform::ALL.iter().map(|t| self.transform(*t))
+    }
+
+    /// The cells from `min` to `max` (inclusive), clamped to the grid.
+    pub fn crop(&self, min: &Pos, max: &Pos) -> Grid2D<T> {
+        let min = (min.0.max(0), min.1.max(0));
+        let max = (max.0.min(self.bounds().0), max.1.min(self.bounds().1));
+        if min.0 > max.0 || min.1 > max.1 {
+            return Grid2D::from_rows(vec![]);
+        }
+        let width = (max.0 - min.0 + 1) as usize;
+        let height = (max.1 - min.1 + 1) as usize;
+        Grid2D::from_fn(width, height, |pos| {
+            self[(pos.0 + min.0, pos.1 + min.1)].clone()
+        })
+    }
+}
+
+impl Grid {
+    pub fn transform(&self, transform: Transform) -> Grid {
+        let map = self
+            .map
+            .iter()
+            .map(|(c, positions)| {
+                let positions = positions
+                    .iter()
+                    .map(|pos| transform.apply(pos, &self.bounds))
+                    .collect();
+                (*c, positions)
+            })
+            .collect();
+        Grid {
+            map,
+            bounds: transform.bounds(&self.bounds),
+        }
+    }
+
+    /// All 8 symmetries of the grid, in the order of [`Transform::ALL`].
+    pub fn symmetries(&self) -> impl Iterator<Item = Grid> + '_ {
+        Transform::ALL.iter().map(|t| self.transform(*t))
+    }
+
+    /// The positions from `min` to `max` (inclusive), moved so that `min` becomes (0, 0).
+    /// A crop that misses the grid is empty, with bounds (-1, -1) as in [`Grid2D::crop`].
+    pub fn crop(&self, min: &Pos, max: &Pos) -> Grid {
+        let min = (min.0.max(0), min.1.max(0));
+        let max = (max.0.min(self.bounds.0), max.1.min(self.bounds.1));
+        if min.0 > max.0 || min.1 > max.1 {
+            return Grid {
+                bounds: (-1, -1),
+                ..Grid::default()
+            };
+        }
+        let inside =
+            |pos: &Pos| (min.0..=max.0).contains(&pos.0) && (min.1..=max.1).contains(&pos.1);
+
+        let mut grid = Grid {
+            bounds: (max.0 - min.0, max.1 - min.1),
+            ..Grid::default()
+        };
+        for (c, positions) in &self.map {
+            for pos in positions.iter().filter(|pos| inside(pos)) {
+                grid.update(*c, (pos.0 - min.0, pos.1 - min.1));
+            }
+        }
+        grid
+    }
+}
+
+/// Applies a symmetry to rectangular 2D data, e.g. the lines of a puzzle input.
+pub fn transform<T: Clone>(data: &[Vec<T>], transform: Transform) -> Vec<Vec<T>> {
+    Grid2D::from_rows(data.to_vec())
+        .transform(transform)
+        .rows()
+        .map(<[T]>::to_vec)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{grid_of, rotate, transpose};
+    use aoc_parse::{parser, Parser};
+
+    fn sample() -> Grid2D<char> {
+        Grid2D::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']])
+    }
+
+    #[test]
+    fn test_transforms_non_square() {
+        let grid = sample();
+        let render = |t: Transform| grid.transform(t).to_string();
+        assert_eq!(render(Transform::Identity), "abc\ndef\n");
+        assert_eq!(render(Transform::Rotate90), "da\neb\nfc\n");
+        assert_eq!(render(Transform::Rotate180), "fed\ncba\n");
+        assert_eq!(render(Transform::Rotate270), "cf\nbe\nad\n");
+        assert_eq!(render(Transform::FlipHorizontal), "cba\nfed\n");
+        assert_eq!(render(Transform::FlipVertical), "def\nabc\n");
+        assert_eq!(render(Transform::Transpose), "ad\nbe\ncf\n");
+        assert_eq!(render(Transform::AntiTranspose), "fc\neb\nda\n");
+    }
+
+    #[test]
+    fn test_inverse() {
+        let grid = sample();
+        for t in Transform::ALL {
+            assert_eq!(grid.transform(t).transform(t.inverse()), grid);
+        }
+        assert_eq!(grid.symmetries().count(), 8);
+    }
+
+    #[test]
+    fn test_crop() {
+        let grid = sample();
+        assert_eq!(grid.crop(&(1, 0), &(5, 1)).to_string(), "bc\nef\n");
+        let empty = grid.crop(&(2, 2), &(3, 3));
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let grid = parser!(grid_of(".#")).parse("#..\n..#\n").unwrap();
+        let rotated = grid.transform(Transform::Rotate90);
+        assert_eq!(rotated.to_string(), ".#\n..\n#.\n");
+        assert_eq!(rotated.bounds, (1, 2));
+        assert_eq!(grid.crop(&(1, 1), &(2, 1)).to_string(), ".#\n");
+        let outside = grid.crop(&(5, 5), &(7, 7));
+        assert!(outside.map.is_empty());
+        assert_eq!(outside.bounds, (-1, -1));
+        assert_eq!(outside.to_string(), "");
+    }
+
+    #[test]
+    fn test_vec_data() {
+        let data = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(rotate(&data), vec![vec![4, 1], vec![5, 2], vec![6, 3]]);
+        assert_eq!(transform(&data, Transform::Transpose), transpose(&data));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{Grid, Pos};
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(Vec::new());
+
+/// Pushes a frame for `solve --visualize`. The closure is only called when
+/// visualization is enabled, so building the frame costs nothing otherwise:
+///
+/// ```
+/// visualize(|| {
+///     let mut frame = Frame::new(self.bounds).label(format!("move {i}"));
+///     frame.set_all(&self.walls, '#');
+///     frame.set_colored(&self.robot, '@', Color::RED);
+///     frame
+/// });
+/// ```
+#[inline]
+pub fn visualize<F>(frame: F)
+where
+    F: FnOnce() -> Frame,
+{
+    if is_visualizing() {
+        FRAMES.lock().unwrap().push(frame());
+    }
+}
+
+#[inline]
+pub fn is_visualizing() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_visualizing(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Removes and returns all frames pushed so far.
+pub fn take_frames() -> Vec<Frame> {
+    std::mem::take(&mut *FRAMES.lock().unwrap())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const GRAY: Color = Color(128, 128, 128);
+    pub const RED: Color = Color(230, 60, 60);
+    pub const GREEN: Color = Color(60, 200, 80);
+    pub const BLUE: Color = Color(70, 110, 230);
+    pub const YELLOW: Color = Color(240, 210, 60);
+    pub const ORANGE: Color = Color(240, 140, 40);
+    pub const PURPLE: Color = Color(170, 90, 220);
+    pub const CYAN: Color = Color(60, 200, 220);
+
+    /// Color used for cells without an explicit color when rendering images.
+    pub fn default_for(c: char) -> Color {
+        const PALETTE: [Color; 7] = [
+            Color::GREEN,
+            Color::BLUE,
+            Color::YELLOW,
+            Color::ORANGE,
+            Color::PURPLE,
+            Color::CYAN,
+            Color::RED,
+        ];
+        match c {
+            ' ' | '.' => Color::BLACK,
+            '#' => Color::GRAY,
+            _ => PALETTE[c as usize % PALETTE.len()],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub c: char,
+    pub color: Option<Color>,
+}
+
+/// A rectangular char/color buffer with a step label, as pushed by [`visualize`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub label: String,
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    /// Creates an empty ('.') frame. As with [`Grid`], `bounds` is the largest
+    /// position inside the frame.
+    pub fn new(bounds: Pos) -> Self {
+        let width = (bounds.0 + 1).max(0) as usize;
+        let height = (bounds.1 + 1).max(0) as usize;
+        Self {
+            label: String::new(),
+            width,
+            height,
+            cells: vec![
+                Cell {
+                    c: '.',
+                    color: None
+                };
+                width * height
+            ],
+        }
+    }
+
+    /// Creates a frame with every position of the grid set to its char.
+    pub fn from_grid(grid: &Grid) -> Self {
+        let mut frame = Self::new(grid.bounds);
+        for (c, positions) in &grid.map {
+            frame.set_all(positions, *c);
+        }
+        frame
+    }
+
+    pub fn label<S: Into<String>>(mut self, label: S) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets the char at a position. Positions outside the frame are ignored.
+    pub fn set(&mut self, pos: &Pos, c: char) {
+        if let Some(i) = self.index(pos) {
+            self.cells[i] = Cell { c, color: None };
+        }
+    }
+
+    /// Sets the char and color at a position. Positions outside the frame are ignored.
+    pub fn set_colored(&mut self, pos: &Pos, c: char, color: Color) {
+        if let Some(i) = self.index(pos) {
+            self.cells[i] = Cell {
+                c,
+                color: Some(color),
+            };
+        }
+    }
+
+    pub fn set_all<'a, I>(&mut self, positions: I, c: char)
+    where
+        I: IntoIterator<Item = &'a Pos>,
+    {
+        for pos in positions {
+            self.set(pos, c);
+        }
+    }
+
+    pub fn get(&self, pos: &Pos) -> Option<&Cell> {
+        self.index(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    fn index(&self, pos: &Pos) -> Option<usize> {
+        let (x, y) = (usize::try_from(pos.0).ok()?, usize::try_from(pos.1).ok()?);
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.label.is_empty() {
+            writeln!(f, "{}", self.label)?;
+        }
+        for row in self.rows() {
+            writeln!(f, "{}", row.iter().map(|cell| cell.c).collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_display() {
+        let mut frame = Frame::new((3, 1)).label("step 1");
+        frame.set(&(0, 0), '#');
+        frame.set_colored(&(2, 1), '@', Color::RED);
+        frame.set(&(4, 0), 'X');
+        frame.set(&(-1, 0), 'X');
+        assert_eq!(frame.to_string(), "step 1\n#...\n..@.\n");
+        assert_eq!(frame.get(&(2, 1)).unwrap().color, Some(Color::RED));
+        assert_eq!(frame.get(&(4, 0)), None);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let mut grid = Grid::default();
+        grid.update('#', (0, 0));
+        grid.update('O', (1, 1));
+        grid.update_bounds(&(2, 1));
+        assert_eq!(Frame::from_grid(&grid).to_string(), "#..\n.O.\n");
+    }
+}